@@ -0,0 +1,142 @@
+//! Sparse grids of values keyed by integer coordinates.
+use std::collections::hash_map::{self, HashMap};
+
+use crate::int::*;
+
+macro_rules! sparse_grids {
+    ($($n:ident => ($vt:ident, $aabb:ident, $neighbors:ident, |$cell:ident| $adjacent:expr)),+) => {
+        $(
+        /// A sparse grid storing a value for each occupied cell, backed by a `HashMap`.
+        #[derive(Clone, Debug)]
+        pub struct $n<T> {
+            cells: HashMap<$vt, T>,
+        }
+
+        impl<T> $n<T> {
+            #[inline]
+            pub fn new() -> Self {
+                $n { cells: HashMap::new() }
+            }
+
+            #[inline]
+            pub fn get(&self, cell: $vt) -> Option<&T> {
+                self.cells.get(&cell)
+            }
+
+            #[inline]
+            pub fn get_mut(&mut self, cell: $vt) -> Option<&mut T> {
+                self.cells.get_mut(&cell)
+            }
+
+            /// Store `value` at `cell`, returning the value previously stored there, if any.
+            #[inline]
+            pub fn insert(&mut self, cell: $vt, value: T) -> Option<T> {
+                self.cells.insert(cell, value)
+            }
+
+            #[inline]
+            pub fn remove(&mut self, cell: $vt) -> Option<T> {
+                self.cells.remove(&cell)
+            }
+
+            #[inline]
+            pub fn contains(&self, cell: $vt) -> bool {
+                self.cells.contains_key(&cell)
+            }
+
+            /// The number of occupied cells.
+            #[inline]
+            pub fn len(&self) -> usize {
+                self.cells.len()
+            }
+
+            #[inline]
+            pub fn is_empty(&self) -> bool {
+                self.cells.is_empty()
+            }
+
+            /// Iterate all occupied cells and their values, in arbitrary order.
+            #[inline]
+            pub fn iter(&self) -> impl Iterator<Item = ($vt, &T)> {
+                self.cells.iter().map(|(cell, value)| (*cell, value))
+            }
+
+            /// Iterate the occupied cells which share a face with `cell`, and their values.
+            #[inline]
+            pub fn $neighbors(&self, $cell: $vt) -> impl Iterator<Item = ($vt, &T)> {
+                $adjacent.filter_map(move |neighbor| self.cells.get(&neighbor).map(|value| (neighbor, value)))
+            }
+
+            /// The smallest bounding box containing every occupied cell, or `None` if the
+            /// grid is empty.
+            #[inline]
+            pub fn bounds(&self) -> Option<$aabb> {
+                $aabb::from_points(self.cells.keys().copied())
+            }
+        }
+
+        impl<T> Default for $n<T> {
+            #[inline]
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<'a, T> IntoIterator for &'a $n<T> {
+            type Item = (&'a $vt, &'a T);
+            type IntoIter = hash_map::Iter<'a, $vt, T>;
+
+            #[inline]
+            fn into_iter(self) -> Self::IntoIter {
+                self.cells.iter()
+            }
+        }
+        )+
+    };
+}
+
+sparse_grids!(
+    SparseGrid2 => (Vec2i, IntAabb2, neighbors_4, |cell| Direction4::ALL.iter().map(move |dir| cell + dir.to_offset())),
+    SparseGrid3 => (Vec3i, IntAabb3, neighbors_6, |cell| cell.neighbors_6())
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn sparse_grid_cells() {
+        let mut grid = SparseGrid2::new();
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.insert(Vec2i::new(0, 0), 'a'), None);
+        grid.insert(Vec2i::new(1, 0), 'b');
+        grid.insert(Vec2i::new(0, -3), 'c');
+        grid.insert(Vec2i::new(1, 1), 'd');
+        assert_eq!(grid.insert(Vec2i::new(1, 1), 'e'), Some('d'));
+        assert_eq!(grid.len(), 4);
+        assert_eq!(grid.get(Vec2i::new(1, 1)), Some(&'e'));
+        assert_eq!(grid.remove(Vec2i::new(0, -3)), Some('c'));
+        assert!(!grid.contains(Vec2i::new(0, -3)));
+
+        let mut neighbors: Vec<_> = grid.neighbors_4(Vec2i::new(1, 0)).collect();
+        neighbors.sort_by_key(|(cell, _)| (cell.x, cell.y));
+        assert_eq!(neighbors, vec![(Vec2i::new(0, 0), &'a'), (Vec2i::new(1, 1), &'e')]);
+        assert_eq!(grid.bounds(), Some(IntAabb2::new(Vec2i::new(0, 0), Vec2i::new(1, 1))));
+    }
+
+    #[test]
+    pub fn sparse_grid_3d_neighbors() {
+        let mut grid = SparseGrid3::default();
+        for offset in Vec3i::zero().neighbors_6() {
+            grid.insert(offset * 2, ());
+        }
+        grid.insert(Vec3i::new(1, 1, 0), ());
+        assert_eq!(grid.neighbors_6(Vec3i::new(1, 0, 0)).count(), 2);
+        assert_eq!(grid.neighbors_6(Vec3i::new(5, 5, 5)).count(), 0);
+        assert_eq!(
+            grid.bounds(),
+            Some(IntAabb3::new(Vec3i::broadcast(-2), Vec3i::broadcast(2)))
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! Implementations of the `approx` crate's comparison traits for the float vectors, enabled
+//! by the `approx` feature. These make it easy to test integer to float conversions with
+//! `assert_abs_diff_eq!` and friends.
+use approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+use crate::vec::*;
+
+macro_rules! impl_approx {
+    ($($n:ident => ($($field:ident),+)),+) => {
+        $(
+        impl AbsDiffEq for $n {
+            type Epsilon = f32;
+
+            #[inline]
+            fn default_epsilon() -> f32 {
+                f32::default_epsilon()
+            }
+
+            #[inline]
+            fn abs_diff_eq(&self, other: &Self, epsilon: f32) -> bool {
+                $(self.$field.abs_diff_eq(&other.$field, epsilon))&&+
+            }
+        }
+
+        impl RelativeEq for $n {
+            #[inline]
+            fn default_max_relative() -> f32 {
+                f32::default_max_relative()
+            }
+
+            #[inline]
+            fn relative_eq(&self, other: &Self, epsilon: f32, max_relative: f32) -> bool {
+                $(self.$field.relative_eq(&other.$field, epsilon, max_relative))&&+
+            }
+        }
+
+        impl UlpsEq for $n {
+            #[inline]
+            fn default_max_ulps() -> u32 {
+                f32::default_max_ulps()
+            }
+
+            #[inline]
+            fn ulps_eq(&self, other: &Self, epsilon: f32, max_ulps: u32) -> bool {
+                $(self.$field.ulps_eq(&other.$field, epsilon, max_ulps))&&+
+            }
+        }
+        )+
+    };
+}
+
+impl_approx!(Vec2 => (x, y), Vec3 => (x, y, z), Vec4 => (x, y, z, w));
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::int::*;
+    use approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne, assert_ulps_eq};
+
+    #[test]
+    pub fn converted_int_vectors() {
+        let v = Vec3::from(Vec3i::new(1, -2, 3).to_f32_array());
+        assert_abs_diff_eq!(v, Vec3::new(1.0, -2.0, 3.0));
+        assert_abs_diff_eq!(v, Vec3::new(1.05, -2.0, 3.0), epsilon = 0.1);
+        assert_relative_ne!(v, Vec3::new(1.05, -2.0, 3.0));
+
+        let big = Vec2::from(Vec2u::new(1 << 24, 1 << 24 | 1).to_f32_array());
+        assert_relative_eq!(big, Vec2::new(16_777_216.0, 16_777_216.0));
+        assert_ulps_eq!(Vec4::from(Vec4i::new(0, 1, 2, 3).to_f32_array()), Vec4::new(0.0, 1.0, 2.0, 3.0));
+    }
+}
@@ -0,0 +1,60 @@
+//! Conversions between the integer vectors and `glam`'s `IVec` and `UVec` types, enabled by the
+//! `glam` feature.
+use glam::{IVec2, IVec3, IVec4, UVec2, UVec3, UVec4};
+
+use crate::int::*;
+
+macro_rules! impl_glam {
+    ($($n:ident <=> $glam:ident => ($($field:ident),+)),+) => {
+        $(
+        impl From<$n> for $glam {
+            #[inline]
+            fn from(v: $n) -> Self {
+                $glam::new($(v.$field),+)
+            }
+        }
+
+        impl From<$glam> for $n {
+            #[inline]
+            fn from(v: $glam) -> Self {
+                $n { $($field: v.$field),+ }
+            }
+        }
+        )+
+    };
+}
+
+impl_glam!(
+    Vec2u <=> UVec2 => (x, y),
+    Vec3u <=> UVec3 => (x, y, z),
+    Vec4u <=> UVec4 => (x, y, z, w),
+    Vec2i <=> IVec2 => (x, y),
+    Vec3i <=> IVec3 => (x, y, z),
+    Vec4i <=> IVec4 => (x, y, z, w)
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn glam_round_trip() {
+        let v2 = Vec2i::new(1, -2);
+        assert_eq!(IVec2::from(v2), IVec2::new(1, -2));
+        assert_eq!(Vec2i::from(IVec2::from(v2)), v2);
+        let v3 = Vec3i::new(-3, 4, i32::MIN);
+        assert_eq!(IVec3::from(v3), IVec3::new(-3, 4, i32::MIN));
+        assert_eq!(Vec3i::from(IVec3::from(v3)), v3);
+        let v4 = Vec4i::new(5, -6, 7, i32::MAX);
+        assert_eq!(IVec4::from(v4), IVec4::new(5, -6, 7, i32::MAX));
+        assert_eq!(Vec4i::from(IVec4::from(v4)), v4);
+
+        let u2 = Vec2u::new(1, u32::MAX);
+        assert_eq!(Vec2u::from(UVec2::from(u2)), u2);
+        let u3 = Vec3u::new(2, 3, 4);
+        assert_eq!(UVec3::from(u3), UVec3::new(2, 3, 4));
+        assert_eq!(Vec3u::from(UVec3::from(u3)), u3);
+        let u4 = Vec4u::new(5u32, 6, 7, 8);
+        assert_eq!(Vec4u::from(UVec4::from(u4)), u4);
+    }
+}
@@ -0,0 +1,71 @@
+//! Conversions between the integer vectors and `nalgebra`'s vectors and points, enabled by the
+//! `nalgebra` feature.
+use nalgebra::{Point2, Point3, Point4, Vector2, Vector3, Vector4};
+
+use crate::int::*;
+
+macro_rules! impl_nalgebra {
+    ($($n:ident => ($vector:ident, $point:ident, $t:ident, ($($field:ident),+))),+) => {
+        $(
+        impl From<$n> for $vector<$t> {
+            #[inline]
+            fn from(v: $n) -> Self {
+                $vector::new($(v.$field),+)
+            }
+        }
+
+        impl From<$vector<$t>> for $n {
+            #[inline]
+            fn from(v: $vector<$t>) -> Self {
+                $n { $($field: v.$field),+ }
+            }
+        }
+
+        impl From<$n> for $point<$t> {
+            #[inline]
+            fn from(v: $n) -> Self {
+                $point::new($(v.$field),+)
+            }
+        }
+
+        impl From<$point<$t>> for $n {
+            #[inline]
+            fn from(p: $point<$t>) -> Self {
+                $n { $($field: p.$field),+ }
+            }
+        }
+        )+
+    };
+}
+
+impl_nalgebra!(
+    Vec2u => (Vector2, Point2, u32, (x, y)),
+    Vec3u => (Vector3, Point3, u32, (x, y, z)),
+    Vec4u => (Vector4, Point4, u32, (x, y, z, w)),
+    Vec2i => (Vector2, Point2, i32, (x, y)),
+    Vec3i => (Vector3, Point3, i32, (x, y, z)),
+    Vec4i => (Vector4, Point4, i32, (x, y, z, w))
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn nalgebra_round_trip() {
+        let v = Vec3i::new(1, -2, 3);
+        let vector: Vector3<i32> = v.into();
+        assert_eq!(vector, Vector3::new(1, -2, 3));
+        assert_eq!(Vec3i::from(vector), v);
+        let point: Point3<i32> = v.into();
+        assert_eq!(point, Point3::new(1, -2, 3));
+        assert_eq!(Vec3i::from(point), v);
+
+        let u = Vec2u::new(4, 5);
+        assert_eq!(Vec2u::from(Vector2::from(u)), u);
+        assert_eq!(Vec2u::from(Point2::from(u)), u);
+        let w = Vec4i::new(1, 2, 3, 4);
+        assert_eq!(Vector4::from(w), Vector4::new(1, 2, 3, 4));
+        assert_eq!(Vec4i::from(Point4::from(w)), w);
+    }
+}
@@ -1,4 +1,8 @@
-use std::ops::*;
+use core::borrow::Borrow;
+use core::convert::TryFrom;
+use core::hash::{Hash, Hasher};
+use core::num::TryFromIntError;
+use core::ops::*;
 
 pub trait MulAdd<A = Self, B = Self> {
     /// The resulting type after applying the fused multiply-add.
@@ -25,6 +29,258 @@ impl MulAdd<i32, i32> for i32 {
     }
 }
 
+/// A distance metric on the integer grid, for use with the `distance` method on the integer
+/// vectors, e.g. `a.distance::<Manhattan>(b)`.
+pub trait Metric {
+    /// Combine the absolute differences along each axis into a single distance.
+    fn combine(abs_diffs: &[u64]) -> u128;
+}
+
+/// The taxicab distance, i.e. the sum of the absolute differences along each axis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Manhattan;
+
+impl Metric for Manhattan {
+    #[inline]
+    fn combine(abs_diffs: &[u64]) -> u128 {
+        abs_diffs.iter().map(|&d| u128::from(d)).sum()
+    }
+}
+
+/// The chessboard distance, i.e. the largest absolute difference along any axis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Chebyshev;
+
+impl Metric for Chebyshev {
+    #[inline]
+    fn combine(abs_diffs: &[u64]) -> u128 {
+        abs_diffs.iter().max().map_or(0, |&d| u128::from(d))
+    }
+}
+
+/// The squared Euclidean distance, which unlike the Euclidean distance itself is always an
+/// integer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct EuclideanSq;
+
+impl Metric for EuclideanSq {
+    #[inline]
+    fn combine(abs_diffs: &[u64]) -> u128 {
+        abs_diffs.iter().map(|&d| u128::from(d) * u128::from(d)).sum()
+    }
+}
+
+/// Functionality shared by all of the integer vector types, so that downstream code can be
+/// generic over them with a `T: IntVector` bound.
+///
+/// Each method forwards to the inherent method of the same name.
+pub trait IntVector: Copy + Eq + Add<Output = Self> + Sub<Output = Self> + From<Self::Array> {
+    /// The element type of each component.
+    type Scalar: Copy;
+
+    /// The array type holding all of the components, `[Self::Scalar; Self::DIM]`.
+    type Array: Copy + AsRef<[Self::Scalar]> + AsMut<[Self::Scalar]>;
+
+    /// The number of components.
+    const DIM: usize;
+
+    /// A vector with every component set to `val`.
+    fn broadcast(val: Self::Scalar) -> Self;
+
+    /// The dot product of `self` and `other`.
+    fn dot(&self, other: Self) -> Self::Scalar;
+
+    /// The sum of all components.
+    fn component_sum(&self) -> Self::Scalar;
+
+    /// The components as a slice, in `x`, `y`, `z`, `w` order.
+    fn as_slice(&self) -> &[Self::Scalar];
+
+    /// The components as an array, in `x`, `y`, `z`, `w` order.
+    fn to_array(self) -> Self::Array;
+
+    /// Build a vector from an array of components, the inverse of `to_array`.
+    fn from_array(comps: Self::Array) -> Self {
+        Self::from(comps)
+    }
+}
+
+/// The integer square root of `n`, rounded down, computed bit by bit.
+fn isqrt_u128(n: u128) -> u128 {
+    let mut rem = n;
+    let mut root = 0u128;
+    let mut bit = 1u128 << 126;
+    while bit > n {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if rem >= root + bit {
+            rem -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    root
+}
+
+/// Scales `comps` in place so that their magnitude is approximately `target`, using only
+/// integer math. Each component is rounded to the nearest integer, so the resulting magnitude
+/// is off by at most half the square root of the number of components. A zero vector is left
+/// untouched.
+fn scale_to_magnitude(comps: &mut [i32], target: i64) {
+    let mag_sq: u128 = comps.iter().map(|&c| (i64::from(c) * i64::from(c)) as u128).sum();
+    if mag_sq == 0 {
+        return;
+    }
+    // Shifting by 62 before the root leaves 31 fractional bits in the magnitude while still
+    // fitting in a u128 for up to four i32 components.
+    let mag = isqrt_u128(mag_sq << 62) as i128;
+    for c in comps.iter_mut() {
+        let num = (i128::from(*c) * i128::from(target)) << 31;
+        *c = ((num + num.signum() * (mag / 2)) / mag) as i32;
+    }
+}
+
+/// Divides `n` by `d`, rounding to the nearest integer with ties rounded away from zero.
+#[inline]
+fn div_round(n: i64, d: i64) -> i64 {
+    let (q, r) = (n / d, n % d);
+    if 2 * r.abs() >= d.abs() {
+        q + n.signum() * d.signum()
+    } else {
+        q
+    }
+}
+
+/// Like `div_round`, but in `i128` so that products of two 32 bit values always fit.
+#[inline]
+fn div_round_i128(n: i128, d: i128) -> i128 {
+    let (q, r) = (n / d, n % d);
+    if 2 * r.abs() >= d.abs() {
+        q + n.signum() * d.signum()
+    } else {
+        q
+    }
+}
+
+/// The greatest common divisor of `a` and `b` by Euclid's algorithm, with `gcd(0, n) == n`.
+#[inline]
+fn gcd_u64(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// The least common multiple of `a` and `b`, with `lcm(0, n) == 0`. Cannot overflow for
+/// arguments which fit in 32 bits.
+#[inline]
+fn lcm_u64(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        a / gcd_u64(a, b) * b
+    }
+}
+
+/// Multiplier used by the FxHash combining step in `spatial_hash`.
+const SPATIAL_HASH_SEED: u64 = 0x517c_c1b7_2722_0a95;
+
+/// The 64-bit finalizer (`fmix64`) from MurmurHash3, used to spread the bits of
+/// `spatial_hash` so that nearby coordinates land in distant buckets.
+#[inline]
+fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    k ^ (k >> 33)
+}
+
+/// Rotates and flips the quadrant `(x, y)` of side `side` as required by the Hilbert curve.
+#[inline]
+fn hilbert_rotate(side: u64, x: &mut u64, y: &mut u64, rx: u64, ry: u64) {
+    if ry == 0 {
+        if rx == 1 {
+            *x = side - 1 - *x;
+            *y = side - 1 - *y;
+        }
+        core::mem::swap(x, y);
+    }
+}
+
+/// Mirrors `c` back and forth over `0..size`, like a `MIRRORED_REPEAT` sampler.
+#[inline]
+fn mirror_coord(c: i32, size: i32) -> i32 {
+    let period = 2 * i64::from(size);
+    let m = i64::from(c).rem_euclid(period);
+    (if m < i64::from(size) { m } else { period - 1 - m }) as i32
+}
+
+/// The 8-connected neighbor offsets of a 2d cell paired with their move cost, orthogonal
+/// neighbors first.
+const NEIGHBORS_8_WEIGHTED: [((i32, i32), u32); 8] = [
+    ((1, 0), 10),
+    ((0, 1), 10),
+    ((-1, 0), 10),
+    ((0, -1), 10),
+    ((1, 1), 14),
+    ((-1, 1), 14),
+    ((-1, -1), 14),
+    ((1, -1), 14),
+];
+
+/// Referencing `AssertLen::<N, DIM>::OK` fails to compile unless `N == DIM`.
+struct AssertLen<const N: usize, const DIM: usize>;
+
+impl<const N: usize, const DIM: usize> AssertLen<N, DIM> {
+    const OK: () = assert!(N == DIM, "Array length does not match the vector's dimension");
+}
+
+macro_rules! bvecs {
+    ($($n:ident => ($($c:ident),+)),+) => {
+        $(
+        /// A set of booleans, one per component of the integer vector of the same dimension,
+        /// used as a component-wise mask.
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        #[repr(C)]
+        pub struct $n {
+            $(pub $c: bool,)+
+        }
+
+        impl $n {
+            #[inline]
+            pub fn new($($c: bool),+) -> Self {
+                $n { $($c),+ }
+            }
+
+            #[inline]
+            pub fn broadcast(val: bool) -> Self {
+                $n { $($c: val),+ }
+            }
+
+            /// Returns true if any component of the mask is set.
+            #[inline]
+            pub fn any(&self) -> bool {
+                false $(|| self.$c)+
+            }
+
+            /// Returns true if every component of the mask is set.
+            #[inline]
+            pub fn all(&self) -> bool {
+                true $(&& self.$c)+
+            }
+        }
+        )+
+    };
+}
+
+bvecs!(BVec2 => (x, y), BVec3 => (x, y, z), BVec4 => (x, y, z, w));
+
 macro_rules! vec2i {
     ($(($n:ident, $v3t:ident, $v4t:ident) => $t:ident),+) => {
         $(
@@ -33,7 +289,7 @@ macro_rules! vec2i {
         /// Generally this distinction between a point and vector is more of a pain than it is worth
         /// to distinguish on a type level, however when converting to and from homogeneous
         /// coordinates it is quite important.
-        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
         #[repr(C)]
         pub struct $n {
             pub x: $t,
@@ -76,6 +332,14 @@ macro_rules! vec2i {
                 $v3t { x: self.x, y: self.y, z: 0 }
             }
 
+            /// Create a homogeneous 2d *direction* from this vector, with a homogeneous component
+            /// of 0. This is an alias of `into_homogeneous_vector`, named to make clear that the
+            /// result is unaffected by translation.
+            #[inline]
+            pub fn into_homogeneous_direction(self) -> $v3t {
+                self.into_homogeneous_vector()
+            }
+
             /// Create a 2d point from a homogeneous 2d *point*, performing
             /// division by the homogeneous component. This should not be used
             /// for homogeneous 2d *vectors*, which will have 0 as their
@@ -104,7 +368,7 @@ macro_rules! vec2i {
 
             #[inline]
             pub fn mag(&self) -> $t {
-                (self.mag_sq() as f64).sqrt() as $t
+                isqrt_u128(self.mag_sq().max(0) as u128) as $t
             }
 
             #[inline]
@@ -179,453 +443,568 @@ macro_rules! vec2i {
                 self.x.min(self.y)
             }
 
+            /// Like `min_by_component`, but also returns a mask which is set for each component
+            /// taken from `other`. On ties the component from `self` is kept.
             #[inline]
-            pub fn zero() -> Self {
-                Self::broadcast(0)
+            pub fn min_by_component_tracked(&self, other: Self) -> (Self, BVec2) {
+                let mask = BVec2::new(other.x < self.x, other.y < self.y);
+                (self.min_by_component(other), mask)
             }
 
+            /// Like `max_by_component`, but also returns a mask which is set for each component
+            /// taken from `other`. On ties the component from `self` is kept.
             #[inline]
-            pub fn one() -> Self {
-                Self::broadcast(1)
+            pub fn max_by_component_tracked(&self, other: Self) -> (Self, BVec2) {
+                let mask = BVec2::new(other.x > self.x, other.y > self.y);
+                (self.max_by_component(other), mask)
+            }
+
+            /// A fast hash of the coordinates, intended for custom spatial hash maps where the
+            /// default `Hash` implementation and hasher are too slow.
+            ///
+            /// Each component's 32 bits are folded in using the FxHash step (rotate left by 5, xor,
+            /// multiply by `0x517cc1b727220a95`) and the result is finished with the MurmurHash3
+            /// `fmix64` finalizer. The hash is deterministic across platforms and runs.
+            #[inline]
+            pub fn spatial_hash(&self) -> u64 {
+                let mut hash = 0u64;
+                for &c in self.as_slice() {
+                    hash = (hash.rotate_left(5) ^ (c as u64 & 0xffff_ffff)).wrapping_mul(SPATIAL_HASH_SEED);
+                }
+                fmix64(hash)
             }
 
+            /// Promote each component to `f64`, which represents every 32-bit integer exactly.
             #[inline]
-            pub fn xyz(&self) -> $v3t {
-                $v3t::new(self.x, self.y, 0)
+            pub fn to_f64_array(&self) -> [f64; 2] {
+                [self.x as f64, self.y as f64]
             }
 
+            /// Convert each component to `f32`, which rounds integers with a magnitude above `2^24`.
             #[inline]
-            pub fn xyzw(&self) -> $v4t {
-                $v4t::new(self.x, self.y, 0, 0)
+            pub fn to_f32_array(&self) -> [f32; 2] {
+                [self.x as f32, self.y as f32]
             }
 
+            /// Round each component down to a multiple of the corresponding component of `multiple`,
+            /// using Euclidean division so that negative values round towards negative infinity.
+            /// Components with a multiple of zero are left unchanged.
             #[inline]
-            pub fn layout() -> alloc::alloc::Layout {
-                alloc::alloc::Layout::from_size_align(std::mem::size_of::<Self>(), std::mem::align_of::<$t>()).unwrap()
+            pub fn align_down(&self, multiple: Self) -> Self {
+                let align = |c: $t, m: $t| if m == 0 { c } else { c - c.rem_euclid(m) };
+                $n::new(
+                    align(self.x, multiple.x),
+                    align(self.y, multiple.y),
+                )
+            }
+
+            /// Round each component up to a multiple of the corresponding component of `multiple`,
+            /// using Euclidean division so that negative values round towards positive infinity.
+            /// Components with a multiple of zero are left unchanged.
+            #[inline]
+            pub fn align_up(&self, multiple: Self) -> Self {
+                let align = |c: $t, m: $t| {
+                    let r = if m == 0 { 0 } else { c.rem_euclid(m) };
+                    if r == 0 {
+                        c
+                    } else if m > 0 {
+                        c + (m - r)
+                    } else {
+                        c - r - m
+                    }
+                };
+                $n::new(
+                    align(self.x, multiple.x),
+                    align(self.y, multiple.y),
+                )
             }
 
+            /// The components of this vector as little-endian bytes, in `x, y, ...` order.
             #[inline]
-            pub fn as_slice(&self) -> &[$t] {
-                // This is safe because we are statically bounding our slices to the size of these
-                // vectors
-                unsafe {
-                    std::slice::from_raw_parts(self as *const $n as *const $t, 2)
+            pub fn to_le_bytes(&self) -> [u8; 2 * core::mem::size_of::<$t>()] {
+                let mut bytes = [0; 2 * core::mem::size_of::<$t>()];
+                for (chunk, c) in bytes.chunks_exact_mut(core::mem::size_of::<$t>()).zip(self.as_slice()) {
+                    chunk.copy_from_slice(&c.to_le_bytes());
                 }
+                bytes
             }
 
-
+            /// Create a vector from components stored as little-endian bytes, in `x, y, ...` order.
             #[inline]
-            pub fn as_byte_slice(&self) -> &[u8] {
-                // This is safe because we are statically bounding our slices to the size of these
-                // vectors
-                unsafe {
-                    std::slice::from_raw_parts(self as *const $n as *const u8, 2 * std::mem::size_of::<$t>())
+            pub fn from_le_bytes(bytes: [u8; 2 * core::mem::size_of::<$t>()]) -> Self {
+                let mut v = Self::zero();
+                for (c, chunk) in v.as_mut_slice().iter_mut().zip(bytes.chunks_exact(core::mem::size_of::<$t>())) {
+                    let mut comp = [0; core::mem::size_of::<$t>()];
+                    comp.copy_from_slice(chunk);
+                    *c = $t::from_le_bytes(comp);
                 }
+                v
             }
 
+            /// The components of this vector as big-endian bytes, in `x, y, ...` order.
             #[inline]
-            pub fn as_mut_slice(&mut self) -> &mut [$t] {
-                // This is safe because we are statically bounding our slices to the size of these
-                // vectors
-                unsafe {
-                    std::slice::from_raw_parts_mut(self as *mut $n as *mut $t, 2)
+            pub fn to_be_bytes(&self) -> [u8; 2 * core::mem::size_of::<$t>()] {
+                let mut bytes = [0; 2 * core::mem::size_of::<$t>()];
+                for (chunk, c) in bytes.chunks_exact_mut(core::mem::size_of::<$t>()).zip(self.as_slice()) {
+                    chunk.copy_from_slice(&c.to_be_bytes());
                 }
+                bytes
             }
 
+            /// Create a vector from components stored as big-endian bytes, in `x, y, ...` order.
             #[inline]
-            pub fn as_mut_byte_slice(&mut self) -> &mut [u8] {
-                // This is safe because we are statically bounding our slices to the size of these
-                // vectors
-                unsafe {
-                    std::slice::from_raw_parts_mut(self as *mut $n as *mut u8, 2 * std::mem::size_of::<$t>())
+            pub fn from_be_bytes(bytes: [u8; 2 * core::mem::size_of::<$t>()]) -> Self {
+                let mut v = Self::zero();
+                for (c, chunk) in v.as_mut_slice().iter_mut().zip(bytes.chunks_exact(core::mem::size_of::<$t>())) {
+                    let mut comp = [0; core::mem::size_of::<$t>()];
+                    comp.copy_from_slice(chunk);
+                    *c = $t::from_be_bytes(comp);
                 }
+                v
             }
 
-            /// Returns a constant unsafe pointer to the underlying data in the underlying type.
-            /// This function is safe because all types here are repr(C) and can be represented
-            /// as their underlying type.
-            ///
-            /// # Safety
-            ///
-            /// It is up to the caller to correctly use this pointer and its bounds.
+            /// The component along the given axis (0 for `x`, 1 for `y`, ...), or `None` if the axis
+            /// is out of range for this vector.
             #[inline]
-            pub fn as_ptr(&self) -> *const $t {
-                self as *const $n as *const $t
+            pub fn component(&self, axis: usize) -> Option<$t> {
+                self.as_slice().get(axis).copied()
             }
 
-            /// Returns a mutable unsafe pointer to the underlying data in the underlying type.
-            /// This function is safe because all types here are repr(C) and can be represented
-            /// as their underlying type.
+            /// Returns a copy of this vector with the component along the given axis replaced by `value`.
             ///
-            /// # Safety
+            /// # Panics
             ///
-            /// It is up to the caller to correctly use this pointer and its bounds.
+            /// Panics if the axis is out of range for this vector.
             #[inline]
-            pub fn as_mut_ptr(&mut self) -> *mut $t {
-                self as *mut $n as *mut $t
+            pub fn with_component(mut self, axis: usize, value: $t) -> Self {
+                match self.as_mut_slice().get_mut(axis) {
+                    Some(c) => *c = value,
+                    None => panic!("Invalid axis {} for vector of type: {}", axis, core::any::type_name::<$n>()),
+                }
+                self
             }
-        }
 
-        impl From<[$t; 2]> for $n {
+            /// Like `from_homogeneous_point`, but rounds each component to the nearest integer after
+            /// dividing by the homogeneous component instead of truncating towards zero. Ties are
+            /// rounded away from zero.
             #[inline]
-            fn from(comps: [$t; 2]) -> Self {
-                Self::new(comps[0], comps[1])
+            pub fn from_homogeneous_point_rounded(v: $v3t) -> Self {
+                let round = |c: $t| div_round(c as i64, v.z as i64) as $t;
+                Self::new(round(v.x), round(v.y))
             }
-        }
-        impl Into<[$t; 2]> for $n {
+
+            /// The smallest and largest components, `(component_min, component_max)`, computed in a
+            /// single pass.
             #[inline]
-            fn into(self) -> [$t; 2] {
-                [self.x, self.y]
+            pub fn component_min_max(&self) -> ($t, $t) {
+                let (mut min, mut max) = (self.x, self.x);
+                for &c in &[self.y] {
+                    if c < min {
+                        min = c;
+                    } else if c > max {
+                        max = c;
+                    }
+                }
+                (min, max)
             }
-        }
 
-        impl From<&[$t; 2]> for $n {
+            /// Like `mul_add`, but returns `None` if any component of the result does not fit in the
+            /// element type. The multiplication and addition are computed in `i128`, so an
+            /// intermediate product which overflows is fine as long as the final sum fits.
             #[inline]
-            fn from(comps: &[$t; 2]) -> Self {
-                Self::from(*comps)
+            pub fn checked_mul_add(&self, mul: Self, add: Self) -> Option<Self> {
+                let fma = |c: $t, m: $t, a: $t| $t::try_from(c as i128 * m as i128 + a as i128).ok();
+                Some($n { x: fma(self.x, mul.x, add.x)?, y: fma(self.y, mul.y, add.y)? })
             }
-        }
 
-        impl From<&mut [$t; 2]> for $n {
+            /// Returns an iterator over mutable references to the components of this vector, in order.
             #[inline]
-            fn from(comps: &mut [$t; 2]) -> Self {
-                Self::from(*comps)
+            pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, $t> {
+                self.as_mut_slice().iter_mut()
             }
-        }
 
-        impl From<($t, $t)> for $n {
+            /// Returns the per-component median of `a`, `b` and `c`.
+            ///
+            /// Clamping `v` between `lo` and `hi` with `lo <= hi` is equivalent to `median(v, lo, hi)`.
             #[inline]
-            fn from(comps: ($t, $t)) -> Self {
-                Self::new(comps.0, comps.1)
+            pub fn median(a: Self, b: Self, c: Self) -> Self {
+                a.min_by_component(b)
+                    .max_by_component(a.max_by_component(b).min_by_component(c))
             }
-        }
 
-        impl From<&($t, $t)> for $n {
+            /// Convert this vector to another vector type, failing if any component is out of range
+            /// for the target, e.g. `Vec3i::new(1, 2, 3).try_cast::<Vec3u>()`.
             #[inline]
-            fn from(comps: &($t, $t)) -> Self {
-                Self::from(*comps)
+            pub fn try_cast<T: TryFrom<Self>>(&self) -> Result<T, T::Error> {
+                T::try_from(*self)
             }
-        }
 
-        impl From<$n> for ($t, $t) {
-            #[inline]
-            fn from(v: $n) -> Self {
-                (v.x, v.y)
+            /// Compare two vectors under the component-wise partial order.
+            ///
+            /// Returns `Less` if every component of `self` is `<=` the matching component of `other`
+            /// and at least one is strictly less, `Greater` in the symmetric case, `Equal` if all
+            /// components are equal and `None` if the vectors are incomparable.
+            #[inline]
+            pub fn partial_cmp_componentwise(&self, other: Self) -> Option<core::cmp::Ordering> {
+                let mut less = false;
+                let mut greater = false;
+                for (a, b) in self.as_slice().iter().zip(other.as_slice()) {
+                    less |= a < b;
+                    greater |= a > b;
+                }
+                match (less, greater) {
+                    (false, false) => Some(core::cmp::Ordering::Equal),
+                    (true, false) => Some(core::cmp::Ordering::Less),
+                    (false, true) => Some(core::cmp::Ordering::Greater),
+                    (true, true) => None,
+                }
             }
-        }
 
-        impl Add for $n {
-            type Output = Self;
-            #[inline]
-            fn add(self, rhs: $n) -> Self {
-                $n::new(self.x + rhs.x, self.y + rhs.y)
+            /// Compute the weighted sum `Σ wᵢ·vᵢ` of the given `(weight, vector)` terms.
+            ///
+            /// The sum is accumulated in `i128`, so intermediate products and partial sums never
+            /// overflow; only the final result must fit in the element type.
+            ///
+            /// # Panics
+            ///
+            /// Panics if a component of the result does not fit in the element type.
+            #[inline]
+            pub fn weighted_sum(terms: &[($t, Self)]) -> Self {
+                let mut acc = [0i128; 2];
+                for &(weight, v) in terms {
+                    for (a, &c) in acc.iter_mut().zip(v.as_slice()) {
+                        *a += weight as i128 * c as i128;
+                    }
+                }
+                let mut sum = Self::zero();
+                for (c, a) in sum.iter_mut().zip(acc.iter()) {
+                    *c = $t::try_from(*a).expect("Weighted sum component overflows the element type");
+                }
+                sum
             }
-        }
 
-        impl AddAssign for $n {
+            /// Count the components for which `pred` returns true.
             #[inline]
-            fn add_assign(&mut self, rhs: $n) {
-                self.x += rhs.x;
-                self.y += rhs.y;
+            pub fn count_matching<F>(&self, pred: F) -> usize
+                where F: Fn($t) -> bool
+            {
+                self.as_slice().iter().filter(|&&c| pred(c)).count()
             }
-        }
 
-        impl Sub for $n {
-            type Output = Self;
+            /// The squared Euclidean distance between this point and `other`, computed in `u128`
+            /// so that it cannot overflow.
             #[inline]
-            fn sub(self, rhs: $n) -> Self {
-                $n::new(self.x - rhs.x, self.y - rhs.y)
+            pub fn distance_sq_to(&self, other: Self) -> u128 {
+                self.as_slice()
+                    .iter()
+                    .zip(other.as_slice())
+                    .map(|(&a, &b)| {
+                        let d = (a as i64 - b as i64).unsigned_abs() as u128;
+                        d * d
+                    })
+                    .sum()
             }
-        }
 
-        impl SubAssign for $n {
+            /// Returns a comparator ordering points by their squared distance to `reference`, for use
+            /// with `sort_by` and friends.
             #[inline]
-            fn sub_assign(&mut self, rhs: $n) {
-                self.x -= rhs.x;
-                self.y -= rhs.y;
+            pub fn cmp_by_distance_sq(reference: Self) -> impl Fn(&Self, &Self) -> core::cmp::Ordering {
+                move |a, b| a.distance_sq_to(reference).cmp(&b.distance_sq_to(reference))
             }
-        }
 
-        impl Mul for $n {
-            type Output = Self;
+            /// Returns true if every component lies in the half-open range `min..max`, i.e. is at
+            /// least the matching component of `min` and less than that of `max`.
             #[inline]
-            fn mul(self, rhs: $n) -> Self {
-                $n::new(self.x * rhs.x, self.y * rhs.y)
+            pub fn in_bounds(&self, min: Self, max: Self) -> bool {
+                self.as_slice()
+                    .iter()
+                    .zip(min.as_slice().iter().zip(max.as_slice()))
+                    .all(|(c, (lo, hi))| lo <= c && c < hi)
             }
-        }
 
-        impl Mul<$n> for $t {
-            type Output = $n;
+            /// Like `mul_add`, but with the same scalar multiplier for every component, i.e.
+            /// `self * mul + add`.
             #[inline]
-            fn mul(self, rhs: $n) -> $n {
-                $n::new(self * rhs.x, self * rhs.y)
+            pub fn mul_add_scalar(&self, mul: $t, add: $n) -> Self {
+                self.mul_add($n::broadcast(mul), add)
             }
-        }
 
-        impl Mul<$t> for $n {
-            type Output = $n;
+            /// The component at index `i` taken modulo the dimension, so that the components are
+            /// treated as a cyclic buffer and e.g. `-1` returns the last component.
             #[inline]
-            fn mul(self, rhs: $t) -> $n {
-                $n::new(self.x * rhs, self.y * rhs)
+            pub fn wrapping_component(&self, i: isize) -> $t {
+                self.as_slice()[i.rem_euclid(2) as usize]
             }
-        }
 
-        impl MulAssign for $n {
+            /// Like `from_homogeneous_point`, but returns `None` instead of panicking when the
+            /// homogeneous component is zero, i.e. when `v` is a homogeneous 2d *vector* rather than
+            /// a point, or when the division overflows.
             #[inline]
-            fn mul_assign(&mut self, rhs: $n) {
-                self.x *= rhs.x;
-                self.y *= rhs.y;
+            pub fn checked_from_homogeneous_point(v: $v3t) -> Option<Self> {
+                Some(Self { x: v.x.checked_div(v.z)?, y: v.y.checked_div(v.z)? })
             }
-        }
 
-        impl MulAssign<$t> for $n {
+            /// Returns an iterator over the components of this vector, in order. The iterator is
+            /// double-ended, so `v.iter().rev()` walks the components from last to first.
             #[inline]
-            fn mul_assign(&mut self, rhs: $t) {
-                self.x *= rhs;
-                self.y *= rhs;
+            pub fn iter(&self) -> core::slice::Iter<'_, $t> {
+                self.as_slice().iter()
             }
-        }
 
-        impl Div for $n {
-            type Output = Self;
+            /// Returns an iterator over the components of this vector from last to first.
             #[inline]
-            fn div(self, rhs: $n) -> Self {
-                $n::new(self.x / rhs.x, self.y / rhs.y)
+            pub fn iter_rev(&self) -> core::iter::Rev<core::slice::Iter<'_, $t>> {
+                self.iter().rev()
             }
-        }
 
-        impl Div<$t> for $n {
-            type Output = $n;
+            /// The sum of all components.
             #[inline]
-            fn div(self, rhs: $t) -> $n {
-                $n::new(self.x / rhs, self.y / rhs)
+            pub fn component_sum(&self) -> $t {
+                self.x + self.y
             }
-        }
 
-        impl DivAssign for $n {
+            /// Returns an iterator over pairs of matching components of `self` and `other`, in order.
             #[inline]
-            fn div_assign(&mut self, rhs: $n) {
-                self.x /= rhs.x;
-                self.y /= rhs.y;
+            pub fn zip(&self, other: Self) -> impl Iterator<Item = ($t, $t)> {
+                let this = *self;
+                (0..2).map(move |i| (this[i], other[i]))
             }
-        }
 
-        impl DivAssign<$t> for $n {
+            /// Interpolate `percent` percent of the way from `self` to `end`, rounding each component
+            /// to the nearest integer with ties rounded away from `self`. `percent` is clamped to
+            /// `0..=100`, and the math is done in `i64` so it cannot overflow.
             #[inline]
-            fn div_assign(&mut self, rhs: $t) {
-                self.x /= rhs;
-                self.y /= rhs;
+            pub fn lerp_percent(&self, end: Self, percent: $t) -> Self {
+                let percent = i64::from(percent.clamp(0, 100));
+                let mut out = *self;
+                for (c, &e) in out.iter_mut().zip(end.as_slice()) {
+                    let start = i64::from(*c);
+                    *c = (start + div_round((i64::from(e) - start) * percent, 100)) as $t;
+                }
+                out
             }
-        }
 
-        impl Index<usize> for $n {
-            type Output = $t;
+            /// Combine each pair of matching components of `self` and `other` with `f`, collecting
+            /// the results in an array. Unlike `map`, the output may be of any type.
+            #[inline]
+            pub fn map2_to<U, F>(&self, other: Self, f: F) -> [U; 2]
+                where F: Fn($t, $t) -> U
+            {
+                [f(self.x, other.x), f(self.y, other.y)]
+            }
 
-            fn index(&self, index: usize) -> &Self::Output {
-                match index {
-                    0 => &self.x,
-                    1 => &self.y,
-                    _ => panic!("Invalid for vector of type: {}", std::any::type_name::<$n>()),
-                }
-            }
-        }
-
-        impl IndexMut<usize> for $n {
-            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-                match index {
-                    0 => &mut self.x,
-                    1 => &mut self.y,
-                    _ => panic!("Invalid for vector of type: {}", std::any::type_name::<$n>()),
+            /// The Manhattan distance between `self` and `other` on a grid of size `extent` which
+            /// wraps around at its edges, like the surface of a torus. Along each axis the shorter of
+            /// the direct and the wrapped-around difference is taken, and these are summed.
+            ///
+            /// Both points should lie within `0..extent`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if any component of `extent` is not positive, or if the distance does not fit
+            /// in the element type.
+            #[inline]
+            pub fn toroidal_distance(&self, other: Self, extent: Self) -> $t {
+                let mut sum = 0i64;
+                for (i, &e) in extent.as_slice().iter().enumerate() {
+                    assert!(e > 0, "Toroidal extent must be positive on every axis");
+                    let e = i64::from(e);
+                    let d = (i64::from(self[i]) - i64::from(other[i])).rem_euclid(e);
+                    sum += d.min(e - d);
                 }
-            }
-        }
-        )+
-    };
-}
-
-vec2i!((Vec2u, Vec3u, Vec4u) => u32);
-vec2i!((Vec2i, Vec3i, Vec4i) => i32);
-
-macro_rules! vec3i {
-    ($(($v2t:ident, $n:ident, $v4t:ident) => $t:ident),+) => {
-        /// A set of three coordinates which may be interpreted as a point or vector in 3d space,
-        /// or as a homogeneous 2d vector or point.
-        ///
-        /// Generally this distinction between a point and vector is more of a pain than it is worth
-        /// to distinguish on a type level, however when converting to and from homogeneous
-        /// coordinates it is quite important.
-        $(#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
-        #[repr(C)]
-        pub struct $n {
-            pub x: $t,
-            pub y: $t,
-            pub z: $t,
-        }
-
-        impl $n {
-            #[inline]
-            pub fn new(x: $t, y: $t, z: $t) -> Self {
-                $n { x, y, z }
-            }
-
-            #[inline]
-            pub fn broadcast(val: $t) -> Self {
-                Self::new(val, val, val)
-            }
-
-            #[inline]
-            pub fn unit_x() -> Self {
-                $n{ x: 1, y: 0, z: 0 }
-            }
-
-            #[inline]
-            pub fn unit_y() -> Self {
-                $n{ x: 0, y: 1, z: 0 }
-            }
-
-            #[inline]
-            pub fn unit_z() -> Self {
-                $n{ x: 0, y: 0, z: 1 }
-            }
-
-            #[inline]
-            pub fn cross(&self, other: $n) -> Self {
-                $n::new(
-                    self.y.mul_add(other.z, -(self.z as i32) as $t * other.y),
-                    self.z.mul_add(other.x, -(self.x as i32) as $t * other.z),
-                    self.x.mul_add(other.y, -(self.y as i32) as $t * other.x),
-                )
+                $t::try_from(sum).expect("Toroidal distance overflows the element type")
             }
 
-            /// Create a homogeneous 3d *point* from this vector interpreted as a point,
-            /// meaning the homogeneous component will start with a value of 1.
+            /// Convert between Y-up and Y-down coordinates within a viewport of the given height, by
+            /// mapping `y` to `height - 1 - y`. The conversion is its own inverse, so the same call goes
+            /// in either direction.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `height - 1 - y` overflows. For unsigned vectors this means `y` must be less
+            /// than `height`.
             #[inline]
-            pub fn into_homogeneous_point(self) -> $v4t {
-                $v4t { x: self.x, y: self.y, z: self.z, w: 1 }
+            pub fn flip_y_within(&self, height: $t) -> Self {
+                let y = height
+                    .checked_sub(1)
+                    .and_then(|last| last.checked_sub(self.y))
+                    .expect("flip_y_within: y is outside the viewport height");
+                Self { y, ..*self }
             }
 
-            /// Create a homogeneous 3d *vector* from this vector,
-            /// meaning the homogeneous component will always have a value of 0.
+            /// Like `component_sum`, but returns `None` if the sum overflows.
             #[inline]
-            pub fn into_homogeneous_vector(self) -> $v4t {
-                $v4t { x: self.x, y: self.y, z: self.z, w: 0 }
+            pub fn checked_component_sum(&self) -> Option<$t> {
+                self.iter().try_fold(0 as $t, |acc, &c| acc.checked_add(c))
             }
 
-            /// Create a 3d point from a homogeneous 3d *point*, performing
-            /// division by the homogeneous component. This should not be used
-            /// for homogeneous 3d *vectors*, which will have 0 as their
-            /// homogeneous component.
+            /// The product of all components, or `None` if it overflows. This is useful for sizing a
+            /// buffer from an extent before allocating it.
             #[inline]
-            pub fn from_homogeneous_point(v: $v4t) -> Self {
-                Self { x: v.x / v.w, y: v.y / v.w, z: v.z / v.w }
+            pub fn checked_component_product(&self) -> Option<$t> {
+                self.iter().try_fold(1 as $t, |acc, &c| acc.checked_mul(c))
             }
 
-            /// Create a 3d vector from homogeneous 2d *vector*, which simply
-            /// discards the homogeneous component.
+            /// Convert this vector into an array whose length is a const generic parameter, for use
+            /// with code which is generic over the array length. Using a length other than the
+            /// vector's dimension is a compile-time error.
             #[inline]
-            pub fn from_homogeneous_vector(v: $v4t) -> Self {
-                v.into()
+            pub fn into_const_array<const N: usize>(self) -> [$t; N] {
+                let () = AssertLen::<N, 2>::OK;
+                let mut comps = [0 as $t; N];
+                comps.copy_from_slice(self.as_slice());
+                comps
             }
 
-
+            /// Raise each component to the power `exp`, saturating at the bounds of the element
+            /// type instead of overflowing.
             #[inline]
-            pub fn dot(&self, other: $n) -> $t {
-                self.x.mul_add(other.x, self.y.mul_add(other.y, self.z * other.z))
+            pub fn saturating_pow(self, exp: u32) -> Self {
+                $n { x: self.x.saturating_pow(exp), y: self.y.saturating_pow(exp) }
             }
 
+            /// The component-wise greatest common divisor of `self` and `other`, which is always
+            /// non-negative. `gcd(0, n)` is `|n|`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the result does not fit in the element type, which is only possible when
+            /// both components are `0` or `i32::MIN` and at least one is `i32::MIN`.
             #[inline]
-            pub fn reflect(&mut self, normal: $n) {
-                *self -= 2 * self.dot(normal) * normal;
+            pub fn gcd(&self, other: Self) -> Self {
+                self.zip_with_u64(other, gcd_u64, "GCD overflows the element type")
             }
 
+            /// The component-wise least common multiple of `self` and `other`, which is always
+            /// non-negative. `lcm(0, n)` is `0`.
+            ///
+            /// # Panics
+            ///
+            /// The LCM is computed in `u64` and so never overflows internally, but this panics if
+            /// the result does not fit in the element type.
             #[inline]
-            pub fn reflected(&self, normal: $n) -> Self {
-                let mut a = *self;
-                a.reflect(normal);
-                a
+            pub fn lcm(&self, other: Self) -> Self {
+                self.zip_with_u64(other, lcm_u64, "LCM overflows the element type")
             }
 
+            /// Combine the absolute values of matching components with `f`, converting each result
+            /// back to the element type.
             #[inline]
-            pub fn mag(&self) -> $t {
-                (self.mag_sq() as f64).sqrt() as $t
+            fn zip_with_u64(&self, other: Self, f: fn(u64, u64) -> u64, overflow: &str) -> Self {
+                let mut out = *self;
+                for (c, &o) in out.iter_mut().zip(other.as_slice()) {
+                    let r = f((*c as i64).unsigned_abs(), (o as i64).unsigned_abs());
+                    *c = $t::try_from(r).expect(overflow);
+                }
+                out
             }
 
+            /// The unit vector along the given axis (0 for `x`, 1 for `y`, ...), for when the axis
+            /// is only known at runtime.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the axis is out of range for this vector, like `with_component`.
             #[inline]
-            pub fn mag_sq(&self) -> $t {
-                self.x.mul_add(self.x, self.y.mul_add(self.y, self.z * self.z))
+            pub fn unit_axis(axis: usize) -> Self {
+                Self::zero().with_component(axis, 1)
             }
 
+            /// The distance between `self` and `other` under the metric `M`. The per-axis
+            /// differences and the metric are computed in wider types, so only the final distance
+            /// needs to fit in the element type.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the distance does not fit in the element type.
             #[inline]
-            pub fn mul_add(&self, mul: $n, add: $n) -> Self {
-                $n::new(
-                    self.x.mul_add(mul.x, add.x),
-                    self.y.mul_add(mul.y, add.y),
-                    self.z.mul_add(mul.z, add.z),
-                )
+            pub fn distance<M: Metric>(&self, other: Self) -> $t {
+                let mut diffs = [0u64; 2];
+                for (d, (&a, &b)) in diffs.iter_mut().zip(self.as_slice().iter().zip(other.as_slice())) {
+                    *d = (a as i64 - b as i64).unsigned_abs();
+                }
+                $t::try_from(M::combine(&diffs)).expect("Distance overflows the element type")
             }
 
+            /// The index of this cell in a row-major grid of the given width, i.e. `y * width + x`.
+            /// Both coordinates should be non-negative and `x` less than `width`.
             #[inline]
-            pub fn abs(&self) -> Self {
-                Self::new(self.x, self.y, self.z)
+            pub fn to_index(&self, width: $t) -> $t {
+                self.y * width + self.x
             }
 
+            /// The cell at `index` in a row-major grid of the given width. This is the inverse of
+            /// `to_index`.
             #[inline]
-            pub fn clamp(&mut self, min: Self, max: Self) {
-                self.x = self.x.max(min.x).min(max.x);
-                self.y = self.y.max(min.y).min(max.y);
-                self.z = self.z.max(min.z).min(max.z);
+            pub fn from_index(index: $t, width: $t) -> Self {
+                Self { x: index % width, y: index / width }
             }
 
+            /// The index of this cell in a column-major grid of the given height, i.e.
+            /// `x * height + y`. Both coordinates should be non-negative and `y` less than `height`.
             #[inline]
-            pub fn clamped(mut self, min: Self, max: Self) -> Self {
-                self.clamp(min, max);
-                self
+            pub fn to_index_col_major(&self, height: $t) -> $t {
+                self.x * height + self.y
             }
 
+            /// The cell at `index` in a column-major grid of the given height. This is the inverse of
+            /// `to_index_col_major`.
             #[inline]
-            pub fn map<F>(&self, f: F) -> Self
-                where F: Fn($t) -> $t
-            {
-                $n::new(
-                    f(self.x),
-                    f(self.y),
-                    f(self.z)
-                )
+            pub fn from_index_col_major(index: $t, height: $t) -> Self {
+                Self { x: index / height, y: index % height }
             }
 
+            /// Like `apply`, but also passes the index of each component (0 for `x`, 1 for `y`, ...)
+            /// to `f`.
             #[inline]
-            pub fn apply<F>(&mut self, f: F)
-                where F: Fn($t) -> $t
+            pub fn apply_indexed<F>(&mut self, f: F)
+                where F: Fn(usize, $t) -> $t
             {
-                self.x = f(self.x);
-                self.y = f(self.y);
-                self.z = f(self.z);
-            }
-
-            #[inline]
-            pub fn max_by_component(mut self, other: Self) -> Self {
-                self.x = self.x.max(other.x);
-                self.y = self.y.max(other.y);
-                self.z = self.z.max(other.z);
-                self
+                for (i, c) in self.iter_mut().enumerate() {
+                    *c = f(i, *c);
+                }
             }
 
+            /// Build a vector whose `i`th component is the component of `self` at `indices[i]`,
+            /// a swizzle chosen at runtime. Indices may repeat.
+            ///
+            /// # Panics
+            ///
+            /// Panics if any index is out of range for this vector, like indexing does.
             #[inline]
-            pub fn min_by_component(mut self, other: Self) -> Self {
-                self.x = self.x.min(other.x);
-                self.y = self.y.min(other.y);
-                self.z = self.z.min(other.z);
-                self
+            pub fn gather(&self, indices: [usize; 2]) -> Self {
+                $n { x: self[indices[0]], y: self[indices[1]] }
             }
 
+            /// Clamp each component between `min` and `max` like `Ord::clamp`.
+            ///
+            /// Unlike the lenient [`clamped`](Self::clamped), which silently prefers `max` when the
+            /// bounds are inverted, this enforces the same contract as `std`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `min` is greater than `max` on any axis.
             #[inline]
-            pub fn component_max(&self) -> $t {
-                self.x.max(self.y).max(self.z)
+            pub fn clamp_ord(self, min: Self, max: Self) -> Self {
+                assert!(!(min.x > max.x || min.y > max.y), "clamp_ord: min must not exceed max on any axis");
+                self.clamped(min, max)
             }
 
+            /// Scale each component by the fraction `num / den`, rounding to the nearest integer with
+            /// ties rounded away from zero. The product is computed in `i128` so it cannot overflow.
+            ///
+            /// Returns `None` if `den` is zero or a scaled component does not fit in the component type.
             #[inline]
-            pub fn component_min(&self) -> $t {
-                self.x.min(self.y).min(self.z)
+            pub fn scale_rational(&self, num: $t, den: $t) -> Option<Self> {
+                if den == 0 {
+                    return None;
+                }
+                let mut out = *self;
+                for c in out.iter_mut() {
+                    let scaled = div_round_i128(i128::from(*c) * i128::from(num), i128::from(den));
+                    *c = <$t>::try_from(scaled).ok()?;
+                }
+                Some(out)
             }
 
             #[inline]
@@ -638,20 +1017,20 @@ macro_rules! vec3i {
                 Self::broadcast(1)
             }
 
-
             #[inline]
-            pub fn xy(&self) -> $v2t {
-                $v2t::new(self.x, self.y)
+            pub fn xyz(&self) -> $v3t {
+                $v3t::new(self.x, self.y, 0)
             }
 
             #[inline]
             pub fn xyzw(&self) -> $v4t {
-                $v4t::new(self.x, self.y, self.z, 0)
+                $v4t::new(self.x, self.y, 0, 0)
             }
 
+            #[cfg(feature = "alloc")]
             #[inline]
             pub fn layout() -> alloc::alloc::Layout {
-                alloc::alloc::Layout::from_size_align(std::mem::size_of::<Self>(), std::mem::align_of::<$t>()).unwrap()
+                alloc::alloc::Layout::from_size_align(core::mem::size_of::<Self>(), core::mem::align_of::<$t>()).unwrap()
             }
 
             #[inline]
@@ -659,16 +1038,17 @@ macro_rules! vec3i {
                 // This is safe because we are statically bounding our slices to the size of these
                 // vectors
                 unsafe {
-                    std::slice::from_raw_parts(self as *const $n as *const $t, 3)
+                    core::slice::from_raw_parts(self as *const $n as *const $t, 2)
                 }
             }
 
+
             #[inline]
             pub fn as_byte_slice(&self) -> &[u8] {
                 // This is safe because we are statically bounding our slices to the size of these
                 // vectors
                 unsafe {
-                    std::slice::from_raw_parts(self as *const $n as *const u8, 3 * std::mem::size_of::<$t>())
+                    core::slice::from_raw_parts(self as *const $n as *const u8, 2 * core::mem::size_of::<$t>())
                 }
             }
 
@@ -677,7 +1057,7 @@ macro_rules! vec3i {
                 // This is safe because we are statically bounding our slices to the size of these
                 // vectors
                 unsafe {
-                    std::slice::from_raw_parts_mut(self as *mut $n as *mut $t, 3)
+                    core::slice::from_raw_parts_mut(self as *mut $n as *mut $t, 2)
                 }
             }
 
@@ -686,7 +1066,7 @@ macro_rules! vec3i {
                 // This is safe because we are statically bounding our slices to the size of these
                 // vectors
                 unsafe {
-                    std::slice::from_raw_parts_mut(self as *mut $n as *mut u8, 3 * std::mem::size_of::<$t>())
+                    core::slice::from_raw_parts_mut(self as *mut $n as *mut u8, 2 * core::mem::size_of::<$t>())
                 }
             }
 
@@ -715,51 +1095,102 @@ macro_rules! vec3i {
             }
         }
 
-        impl From<[$t; 3]> for $n {
+        impl From<[$t; 2]> for $n {
             #[inline]
-            fn from(comps: [$t; 3]) -> Self {
-                Self::new(comps[0], comps[1], comps[2])
+            fn from(comps: [$t; 2]) -> Self {
+                Self::new(comps[0], comps[1])
             }
         }
-        impl Into<[$t; 3]> for $n {
+        impl Into<[$t; 2]> for $n {
             #[inline]
-            fn into(self) -> [$t; 3] {
-                [self.x, self.y, self.z]
+            fn into(self) -> [$t; 2] {
+                [self.x, self.y]
             }
         }
 
-        impl From<&[$t; 3]> for $n {
+        // Hashing goes through the component array so that a vector and the array it borrows as
+        // hash identically, as required by `Borrow`.
+        impl Hash for $n {
             #[inline]
-            fn from(comps: &[$t; 3]) -> Self {
-               Self::from(*comps)
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                let comps: &[$t; 2] = self.borrow();
+                comps.hash(state);
             }
         }
 
-        impl From<&mut [$t; 3]> for $n {
+        impl Borrow<[$t; 2]> for $n {
             #[inline]
-            fn from(comps: &mut [$t; 3]) -> Self {
+            fn borrow(&self) -> &[$t; 2] {
+                // This is safe because the vector is repr(C) and consists of exactly 2
+                // components of the element type
+                unsafe { &*(self as *const $n as *const [$t; 2]) }
+            }
+        }
+
+        impl From<&[$t; 2]> for $n {
+            #[inline]
+            fn from(comps: &[$t; 2]) -> Self {
                 Self::from(*comps)
             }
         }
 
-       impl From<($t, $t, $t)> for $n {
+        impl From<&mut [$t; 2]> for $n {
             #[inline]
-            fn from(comps: ($t, $t, $t)) -> Self {
-                Self::new(comps.0, comps.1, comps.2)
+            fn from(comps: &mut [$t; 2]) -> Self {
+                Self::from(*comps)
             }
         }
 
-        impl From<&($t, $t, $t)> for $n {
+        impl From<($t, $t)> for $n {
             #[inline]
-            fn from(comps: &($t, $t, $t)) -> Self {
+            fn from(comps: ($t, $t)) -> Self {
+                Self::new(comps.0, comps.1)
+            }
+        }
+
+        impl From<&($t, $t)> for $n {
+            #[inline]
+            fn from(comps: &($t, $t)) -> Self {
                 Self::from(*comps)
             }
         }
 
-        impl From<$n> for ($t, $t, $t) {
+        impl From<$n> for ($t, $t) {
             #[inline]
             fn from(v: $n) -> Self {
-                (v.x, v.y, v.z)
+                (v.x, v.y)
+            }
+        }
+
+        impl From<(u8, u8)> for $n {
+            #[inline]
+            fn from(comps: (u8, u8)) -> Self {
+                $n { x: comps.0.into(), y: comps.1.into() }
+            }
+        }
+
+        impl From<(u16, u16)> for $n {
+            #[inline]
+            fn from(comps: (u16, u16)) -> Self {
+                $n { x: comps.0.into(), y: comps.1.into() }
+            }
+        }
+
+        impl TryFrom<(i64, i64)> for $n {
+            type Error = TryFromIntError;
+
+            #[inline]
+            fn try_from(comps: (i64, i64)) -> Result<Self, Self::Error> {
+                Ok($n { x: $t::try_from(comps.0)?, y: $t::try_from(comps.1)? })
+            }
+        }
+
+        impl TryFrom<(u64, u64)> for $n {
+            type Error = TryFromIntError;
+
+            #[inline]
+            fn try_from(comps: (u64, u64)) -> Result<Self, Self::Error> {
+                Ok($n { x: $t::try_from(comps.0)?, y: $t::try_from(comps.1)? })
             }
         }
 
@@ -767,7 +1198,7 @@ macro_rules! vec3i {
             type Output = Self;
             #[inline]
             fn add(self, rhs: $n) -> Self {
-                $n::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+                $n::new(self.x + rhs.x, self.y + rhs.y)
             }
         }
 
@@ -776,7 +1207,6 @@ macro_rules! vec3i {
             fn add_assign(&mut self, rhs: $n) {
                 self.x += rhs.x;
                 self.y += rhs.y;
-                self.z += rhs.z;
             }
         }
 
@@ -784,7 +1214,7 @@ macro_rules! vec3i {
             type Output = Self;
             #[inline]
             fn sub(self, rhs: $n) -> Self {
-                $n::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+                $n::new(self.x - rhs.x, self.y - rhs.y)
             }
         }
 
@@ -793,7 +1223,6 @@ macro_rules! vec3i {
             fn sub_assign(&mut self, rhs: $n) {
                 self.x -= rhs.x;
                 self.y -= rhs.y;
-                self.z -= rhs.z;
             }
         }
 
@@ -801,7 +1230,7 @@ macro_rules! vec3i {
             type Output = Self;
             #[inline]
             fn mul(self, rhs: $n) -> Self {
-                $n::new(self.x * rhs.x, self.y * rhs.y, self.z * rhs.z)
+                $n::new(self.x * rhs.x, self.y * rhs.y)
             }
         }
 
@@ -809,7 +1238,7 @@ macro_rules! vec3i {
             type Output = $n;
             #[inline]
             fn mul(self, rhs: $n) -> $n {
-                $n::new(self * rhs.x, self * rhs.y, self * rhs.z)
+                $n::new(self * rhs.x, self * rhs.y)
             }
         }
 
@@ -817,7 +1246,7 @@ macro_rules! vec3i {
             type Output = $n;
             #[inline]
             fn mul(self, rhs: $t) -> $n {
-                $n::new(self.x * rhs, self.y * rhs, self.z * rhs)
+                $n::new(self.x * rhs, self.y * rhs)
             }
         }
 
@@ -826,7 +1255,6 @@ macro_rules! vec3i {
             fn mul_assign(&mut self, rhs: $n) {
                 self.x *= rhs.x;
                 self.y *= rhs.y;
-                self.z *= rhs.z;
             }
         }
 
@@ -835,7 +1263,6 @@ macro_rules! vec3i {
             fn mul_assign(&mut self, rhs: $t) {
                 self.x *= rhs;
                 self.y *= rhs;
-                self.z *= rhs;
             }
         }
 
@@ -843,7 +1270,7 @@ macro_rules! vec3i {
             type Output = Self;
             #[inline]
             fn div(self, rhs: $n) -> Self {
-                $n::new(self.x / rhs.x, self.y / rhs.y, self.z / rhs.z)
+                $n::new(self.x / rhs.x, self.y / rhs.y)
             }
         }
 
@@ -851,7 +1278,7 @@ macro_rules! vec3i {
             type Output = $n;
             #[inline]
             fn div(self, rhs: $t) -> $n {
-                $n::new(self.x / rhs, self.y / rhs, self.z / rhs)
+                $n::new(self.x / rhs, self.y / rhs)
             }
         }
 
@@ -860,7 +1287,6 @@ macro_rules! vec3i {
             fn div_assign(&mut self, rhs: $n) {
                 self.x /= rhs.x;
                 self.y /= rhs.y;
-                self.z /= rhs.z;
             }
         }
 
@@ -869,7 +1295,46 @@ macro_rules! vec3i {
             fn div_assign(&mut self, rhs: $t) {
                 self.x /= rhs;
                 self.y /= rhs;
-                self.z /= rhs;
+            }
+        }
+
+        impl MulAdd for $n {
+            type Output = Self;
+
+            #[inline]
+            fn mul_add(self, a: $n, b: $n) -> Self::Output {
+                $n::mul_add(&self, a, b)
+            }
+        }
+
+        impl IntVector for $n {
+            type Scalar = $t;
+            type Array = [$t; 2];
+            const DIM: usize = 2;
+
+            #[inline]
+            fn broadcast(val: $t) -> Self {
+                $n::broadcast(val)
+            }
+
+            #[inline]
+            fn dot(&self, other: Self) -> $t {
+                $n::dot(self, other)
+            }
+
+            #[inline]
+            fn component_sum(&self) -> $t {
+                $n::component_sum(self)
+            }
+
+            #[inline]
+            fn as_slice(&self) -> &[$t] {
+                $n::as_slice(self)
+            }
+
+            #[inline]
+            fn to_array(self) -> [$t; 2] {
+                self.into()
             }
         }
 
@@ -880,8 +1345,7 @@ macro_rules! vec3i {
                 match index {
                     0 => &self.x,
                     1 => &self.y,
-                    2 => &self.z,
-                    _ => panic!("Invalid for vector of type: {}", std::any::type_name::<$n>()),
+                    _ => panic!("Invalid for vector of type: {}", core::any::type_name::<$n>()),
                 }
             }
         }
@@ -891,69 +1355,617 @@ macro_rules! vec3i {
                 match index {
                     0 => &mut self.x,
                     1 => &mut self.y,
-                    2 => &mut self.z,
-                    _ => panic!("Invalid for vector of type: {}", std::any::type_name::<$n>()),
+                    _ => panic!("Invalid for vector of type: {}", core::any::type_name::<$n>()),
                 }
             }
         }
         )+
+    };
+}
+
+vec2i!((Vec2u, Vec3u, Vec4u) => u32);
+vec2i!((Vec2i, Vec3i, Vec4i) => i32);
+
+impl Vec2u {
+    /// Treating `self` as an extent, returns true if `point` lies in `0..self` on every axis.
+    #[inline]
+    pub fn contains_point(&self, point: Self) -> bool {
+        point.x < self.x && point.y < self.y
+    }
+
+    /// Treating `self` as an extent, returns true if `point` lies in `0..=self` on every axis.
+    #[inline]
+    pub fn contains_point_inclusive(&self, point: Self) -> bool {
+        point.x <= self.x && point.y <= self.y
+    }
+
+    /// Apply a signed offset to this position, saturating each component at `0` and `u32::MAX`.
+    #[inline]
+    pub fn offset(&self, delta: Vec2i) -> Self {
+        Self::new(self.x.saturating_add_signed(delta.x), self.y.saturating_add_signed(delta.y))
+    }
+
+    /// Apply a signed offset to this position, returning `None` if any component would
+    /// underflow below zero or overflow past `u32::MAX`.
+    #[inline]
+    pub fn checked_add_signed(&self, delta: Vec2i) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add_signed(delta.x)?,
+            self.y.checked_add_signed(delta.y)?,
+        ))
+    }
+
+    /// The largest component, i.e. the infinity norm of this vector. This is the same as
+    /// `component_max` and exists to mirror the signed vectors.
+    #[inline]
+    pub fn abs_max(&self) -> u32 {
+        self.component_max()
+    }
+
+    /// The smallest component. This is the same as `component_min` and exists to mirror the
+    /// signed vectors.
+    #[inline]
+    pub fn abs_min(&self) -> u32 {
+        self.component_min()
+    }
+
+    /// The distance along the Hilbert curve filling a square of side `2^order` at which this
+    /// point lies. Consecutive indices are always orthogonally adjacent, which gives better
+    /// locality than a row-major layout.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order` is greater than 32 or a component is outside of the square.
+    #[inline]
+    pub fn hilbert_encode(&self, order: u32) -> u64 {
+        assert!(order <= 32, "Hilbert curve order {} is greater than 32", order);
+        let side = 1u64 << order;
+        let (mut x, mut y) = (u64::from(self.x), u64::from(self.y));
+        assert!(x < side && y < side, "Point {:?} is outside of a Hilbert curve of order {}", self, order);
+        let mut index = 0;
+        let mut s = side / 2;
+        while s > 0 {
+            let rx = (x & s > 0) as u64;
+            let ry = (y & s > 0) as u64;
+            index += s * s * ((3 * rx) ^ ry);
+            hilbert_rotate(side, &mut x, &mut y, rx, ry);
+            s /= 2;
+        }
+        index
+    }
+
+    /// The point at distance `index` along the Hilbert curve filling a square of side
+    /// `2^order`. This is the inverse of `hilbert_encode`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `order` is greater than 32 or `index` is past the end of the curve.
+    #[inline]
+    pub fn hilbert_decode(index: u64, order: u32) -> Self {
+        assert!(order <= 32, "Hilbert curve order {} is greater than 32", order);
+        assert!(order == 32 || index < 1 << (2 * order), "Index {} is outside of a Hilbert curve of order {}", index, order);
+        let side = 1u64 << order;
+        let (mut x, mut y) = (0, 0);
+        let mut t = index;
+        let mut s = 1;
+        while s < side {
+            let rx = 1 & (t / 2);
+            let ry = 1 & (t ^ rx);
+            hilbert_rotate(s, &mut x, &mut y, rx, ry);
+            x += s * rx;
+            y += s * ry;
+            t /= 4;
+            s *= 2;
+        }
+        Vec2u::new(x as u32, y as u32)
+    }
+
+    /// The area of the rectangle with this vector as its extent, i.e. `x * y`.
+    ///
+    /// This overflows like any other `u32` multiplication, panicking in debug builds. Use
+    /// `area_wide` if the extent may be large.
+    #[inline]
+    pub fn area(&self) -> u32 {
+        self.x * self.y
+    }
+
+    /// Like `area`, but computed in `u64`, which cannot overflow.
+    #[inline]
+    pub fn area_wide(&self) -> u64 {
+        u64::from(self.x) * u64::from(self.y)
+    }
+
+    /// Rotate the bits of each component left by `n`, wrapping the truncated bits around to the
+    /// other end, unlike the shift operators.
+    #[inline]
+    pub fn rotate_left(self, n: u32) -> Self {
+        Self { x: self.x.rotate_left(n), y: self.y.rotate_left(n) }
+    }
+
+    /// Rotate the bits of each component right by `n`, wrapping the truncated bits around to the
+    /// other end, unlike the shift operators.
+    #[inline]
+    pub fn rotate_right(self, n: u32) -> Self {
+        Self { x: self.x.rotate_right(n), y: self.y.rotate_right(n) }
+    }
+
+    /// Rotate the bits of each component left by the matching component of `amounts`, which
+    /// like `u32::rotate_left` is taken modulo the bit width.
+    #[inline]
+    pub fn rotate_left_componentwise(self, amounts: Vec2u) -> Self {
+        Self { x: self.x.rotate_left(amounts.x), y: self.y.rotate_left(amounts.y) }
+    }
+
+    /// Rotate the bits of each component right by the matching component of `amounts`, which
+    /// like `u32::rotate_right` is taken modulo the bit width.
+    #[inline]
+    pub fn rotate_right_componentwise(self, amounts: Vec2u) -> Self {
+        Self { x: self.x.rotate_right(amounts.x), y: self.y.rotate_right(amounts.y) }
+    }
+
+    /// The wrapping difference `self - rhs`, along with a mask which is set for each component
+    /// that underflowed, i.e. where `self` is less than `rhs`.
+    #[inline]
+    pub fn overflowing_sub(&self, rhs: Self) -> (Self, BVec2) {
+        let (x, x_under) = self.x.overflowing_sub(rhs.x);
+        let (y, y_under) = self.y.overflowing_sub(rhs.y);
+        (Self { x, y }, BVec2::new(x_under, y_under))
+    }
+
+    /// The component-wise bitwise OR of every vector in `vecs`, starting from zero, so an
+    /// empty slice gives zero.
+    #[inline]
+    pub fn fold_or(vecs: &[Vec2u]) -> Vec2u {
+        let mut acc = Vec2u::zero();
+        for v in vecs {
+            acc.x |= v.x;
+            acc.y |= v.y;
+        }
+        acc
+    }
+
+    /// The component-wise bitwise AND of every vector in `vecs`, starting from all ones, so an
+    /// empty slice gives `u32::MAX` in every component.
+    #[inline]
+    pub fn fold_and(vecs: &[Vec2u]) -> Vec2u {
+        let mut acc = Vec2u::broadcast(u32::MAX);
+        for v in vecs {
+            acc.x &= v.x;
+            acc.y &= v.y;
+        }
+        acc
+    }
+
+    /// Map each component from `0..=u32::MAX` to `0.0..=1.0` by dividing by `u32::MAX`, as is
+    /// done to normalize integer colors. The division is done in `f64` before rounding to `f32`.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn to_normalized_f32(&self) -> crate::vec::Vec2 {
+        let norm = |c: u32| (f64::from(c) / f64::from(u32::MAX)) as f32;
+        crate::vec::Vec2::new(norm(self.x), norm(self.y))
     }
 }
 
-vec3i!((Vec2u, Vec3u, Vec4u) => u32);
-vec3i!((Vec2i, Vec3i, Vec4i) => i32);
+impl Vec2i {
+    #[inline]
+    pub fn flip_x(&self) -> Self {
+        Self { x: -self.x, ..*self }
+    }
 
-macro_rules! vec4i {
-    ($($n:ident, $v2t:ident, $v3t:ident => $t:ident),+) => {
-        /// A set of four coordinates which may be interpreted as a point or vector in 4d space,
-        /// or as a homogeneous 3d vector or point.
+    #[inline]
+    pub fn flip_y(&self) -> Self {
+        Self { y: -self.y, ..*self }
+    }
+
+    /// Classify the turn made when travelling from `a` to `b` and then on to `c`.
+    ///
+    /// The cross product of `b - a` and `c - a` is computed in `i128`, so the result is exact
+    /// for all inputs. With the y axis pointing up, `Turn::Left` is a counter-clockwise turn.
+    #[inline]
+    pub fn turn(a: Vec2i, b: Vec2i, c: Vec2i) -> Turn {
+        let (abx, aby) = (i128::from(b.x) - i128::from(a.x), i128::from(b.y) - i128::from(a.y));
+        let (acx, acy) = (i128::from(c.x) - i128::from(a.x), i128::from(c.y) - i128::from(a.y));
+        let cross = abx * acy - aby * acx;
+        if cross > 0 {
+            Turn::Left
+        } else if cross < 0 {
+            Turn::Right
+        } else {
+            Turn::Collinear
+        }
+    }
+
+    /// Returns true if the closed segments `a1`-`a2` and `b1`-`b2` share at least one point,
+    /// including when they only touch at an endpoint or overlap along a common line.
+    ///
+    /// This is built on `turn`, so it is exact for all inputs.
+    #[inline]
+    pub fn segments_intersect(a1: Vec2i, a2: Vec2i, b1: Vec2i, b2: Vec2i) -> bool {
+        // Whether `p`, already known to be collinear with `start` and `end`, lies between them.
+        let within = |start: Vec2i, end: Vec2i, p: Vec2i| {
+            p.clamped(start.min_by_component(end), start.max_by_component(end)) == p
+        };
+        let (o1, o2) = (Vec2i::turn(a1, a2, b1), Vec2i::turn(a1, a2, b2));
+        let (o3, o4) = (Vec2i::turn(b1, b2, a1), Vec2i::turn(b1, b2, a2));
+        if o1 != o2 && o3 != o4 {
+            return true;
+        }
+        (o1 == Turn::Collinear && within(a1, a2, b1))
+            || (o2 == Turn::Collinear && within(a1, a2, b2))
+            || (o3 == Turn::Collinear && within(b1, b2, a1))
+            || (o4 == Turn::Collinear && within(b1, b2, a2))
+    }
+
+    /// Returns true if `other` shares a face with this cell, i.e. the Manhattan distance
+    /// between them is exactly 1.
+    #[inline]
+    pub fn is_adjacent_4(&self, other: Self) -> bool {
+        let d = [(i64::from(self.x) - i64::from(other.x)).unsigned_abs(), (i64::from(self.y) - i64::from(other.y)).unsigned_abs()];
+        d.iter().sum::<u64>() == 1
+    }
+
+    /// Returns true if `other` touches this cell, orthogonally or diagonally, i.e. the
+    /// Chebyshev distance between them is exactly 1.
+    #[inline]
+    pub fn is_adjacent_8(&self, other: Self) -> bool {
+        let d = [(i64::from(self.x) - i64::from(other.x)).unsigned_abs(), (i64::from(self.y) - i64::from(other.y)).unsigned_abs()];
+        d.iter().max() == Some(&1)
+    }
+
+    /// Iterate the cells at exactly `radius` Chebyshev distance from `center`.
+    ///
+    /// A radius of zero yields only `center` and a negative radius yields nothing. Otherwise the
+    /// `8 * radius` cells of the ring are yielded in a fixed order, starting from the
+    /// `(-radius, -radius)` corner and walking along the +x, +y, -x and -y edges in turn. The
+    /// offsets are computed in `i64`, so any radius can be iterated.
+    ///
+    /// # Panics
+    ///
+    /// Panics when reaching a cell whose coordinates do not fit in an `i32`.
+    #[inline]
+    pub fn iter_ring(center: Vec2i, radius: i32) -> impl Iterator<Item = Vec2i> {
+        let radius = i64::from(radius);
+        let count = if radius == 0 { 1 } else { 8 * radius.max(0) };
+        (0..count).map(move |i| {
+            if radius == 0 {
+                return center;
+            }
+            let side_len = 2 * radius;
+            let offset = i % side_len;
+            let (dx, dy) = match i / side_len {
+                0 => (-radius + offset, -radius),
+                1 => (radius, -radius + offset),
+                2 => (radius - offset, radius),
+                _ => (-radius, radius - offset),
+            };
+            let cell = |c: i32, d: i64| i32::try_from(i64::from(c) + d).expect("iter_ring: cell is outside the i32 range");
+            Vec2i::new(cell(center.x, dx), cell(center.y, dy))
+        })
+    }
+
+    /// Iterate every cell within `max_radius` Chebyshev distance of `center`, ring by ring
+    /// outwards starting with `center` itself. Each ring is yielded in the order of `iter_ring`.
+    #[inline]
+    pub fn iter_spiral(center: Vec2i, max_radius: i32) -> impl Iterator<Item = Vec2i> {
+        (0..=max_radius).flat_map(move |radius| Vec2i::iter_ring(center, radius))
+    }
+
+    /// Iterate every cell of the rectangle with corners `a` and `b`, including both corners.
+    ///
+    /// The corners may be given in any order. Cells are yielded row by row, with `x` varying
+    /// fastest, starting from the minimum corner.
+    #[inline]
+    pub fn iter_rect_inclusive(a: Vec2i, b: Vec2i) -> impl Iterator<Item = Vec2i> {
+        let min = a.min_by_component(b);
+        let max = a.max_by_component(b);
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Vec2i::new(x, y)))
+    }
+
+    /// Iterate the 8-connected neighbors of this cell, each paired with the cost of moving to
+    /// it for pathfinding. Orthogonal moves cost `10` and diagonal moves cost `14`, the usual
+    /// integer approximation of `10 * sqrt(2)`. The orthogonal neighbors are yielded first.
+    #[inline]
+    pub fn neighbors_8_weighted(&self) -> impl Iterator<Item = (Vec2i, u32)> {
+        let cell = *self;
+        NEIGHBORS_8_WEIGHTED.iter().map(move |&((dx, dy), cost)| (cell + Vec2i::new(dx, dy), cost))
+    }
+
+    /// Twice the signed area of the polygon with the given vertices, computed with the
+    /// shoelace formula. Doubling keeps the result an exact integer.
+    ///
+    /// The result is positive if the vertices wind counter-clockwise (with `y` pointing up)
+    /// and negative if they wind clockwise. Fewer than three vertices give zero. The sum is
+    /// accumulated in `i128`, so only the final area needs to fit in an `i64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the doubled area does not fit in an `i64`.
+    #[inline]
+    pub fn polygon_area_doubled(points: &[Vec2i]) -> i64 {
+        let mut sum = 0i128;
+        for (i, a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            sum += i128::from(a.x) * i128::from(b.y) - i128::from(b.x) * i128::from(a.y);
+        }
+        i64::try_from(sum).expect("Doubled polygon area overflows i64")
+    }
+
+    /// Returns true if `p` lies inside the polygon with the given vertices, using the nonzero
+    /// winding number rule. Points lying on an edge or vertex count as inside.
+    ///
+    /// The polygon may be concave, self-intersecting, and wind either way. Every test is made
+    /// with `turn`, so the result is exact for all inputs. Fewer than three vertices only
+    /// contain the points on their edges.
+    #[inline]
+    pub fn point_in_polygon(p: Vec2i, vertices: &[Vec2i]) -> bool {
+        let mut winding = 0i64;
+        for (i, &a) in vertices.iter().enumerate() {
+            let b = vertices[(i + 1) % vertices.len()];
+            let turn = Vec2i::turn(a, b, p);
+            if turn == Turn::Collinear && p.clamped(a.min_by_component(b), a.max_by_component(b)) == p {
+                return true;
+            }
+            if a.y <= p.y {
+                if b.y > p.y && turn == Turn::Left {
+                    winding += 1;
+                }
+            } else if b.y <= p.y && turn == Turn::Right {
+                winding -= 1;
+            }
+        }
+        winding != 0
+    }
+
+    /// Iterate every cell inside the polygon with the given vertices, row by row from the
+    /// bottom with `x` increasing along each row.
+    ///
+    /// The cell `(x, y)` is treated as the unit square with that corner, and it is yielded if
+    /// its center `(x + 0.5, y + 0.5)` lies inside the polygon under the even-odd rule. Since
+    /// the vertices are integers no center can lie on a vertex or a horizontal edge, so those
+    /// need no special handling, and for polygons with only axis-aligned edges the number of
+    /// cells yielded equals the area. A center lying exactly on a sloped edge counts as inside
+    /// where that edge bounds the polygon on the left, and outside where it bounds it on the
+    /// right.
+    #[cfg(feature = "alloc")]
+    pub fn fill_polygon(vertices: &[Vec2i]) -> impl Iterator<Item = Vec2i> + '_ {
+        let (min_y, max_y) = vertices
+            .iter()
+            .fold((i32::MAX, i32::MIN), |(lo, hi), v| (lo.min(v.y), hi.max(v.y)));
+        (min_y..max_y).flat_map(move |y| {
+            // Work in doubled coordinates so that cell centers are integers.
+            let row = 2 * i128::from(y) + 1;
+            let mut crossings = alloc::vec::Vec::new();
+            for (i, a) in vertices.iter().enumerate() {
+                let b = vertices[(i + 1) % vertices.len()];
+                let (ay, by) = (2 * i128::from(a.y), 2 * i128::from(b.y));
+                if (ay < row) == (by < row) {
+                    continue;
+                }
+                // The edge crosses the row at doubled x coordinate `num / den`, and the first
+                // cell whose doubled center `2x + 1` is at or past that is `ceil((num - den) / 2den)`.
+                let mut den = i128::from(b.y) - i128::from(a.y);
+                let mut num = 2 * i128::from(a.x) * den + (row - ay) * (i128::from(b.x) - i128::from(a.x));
+                if den < 0 {
+                    num = -num;
+                    den = -den;
+                }
+                crossings.push(-(-(num - den)).div_euclid(2 * den) as i64);
+            }
+            crossings.sort_unstable();
+            (0..crossings.len() / 2)
+                .flat_map(move |i| crossings[2 * i]..crossings[2 * i + 1])
+                .map(move |x| Vec2i::new(x as i32, y))
+        })
+    }
+
+    /// Reflect this point across the line through the origin with direction `dir`.
+    ///
+    /// The reflection of `p` is `2 * (p · d) / (d · d) * d - p`, which lies on the integer
+    /// lattice exactly when `d · d` divides both components of `2 * (p · d) * d`. This is
+    /// always the case for axis-aligned and diagonal directions. Returns `None` if the
+    /// reflection is not integral, if `dir` is zero, or if the result does not fit in an
+    /// `i32`. The math is done in `i128` so intermediate values cannot overflow.
+    #[inline]
+    pub fn reflect_across_line(&self, dir: Vec2i) -> Option<Vec2i> {
+        let (px, py) = (i128::from(self.x), i128::from(self.y));
+        let (dx, dy) = (i128::from(dir.x), i128::from(dir.y));
+        let len_sq = dx * dx + dy * dy;
+        if len_sq == 0 {
+            return None;
+        }
+        let twice_dot = 2 * (px * dx + py * dy);
+        let (nx, ny) = (twice_dot * dx, twice_dot * dy);
+        if nx % len_sq != 0 || ny % len_sq != 0 {
+            return None;
+        }
+        Some(Vec2i::new(
+            i32::try_from(nx / len_sq - px).ok()?,
+            i32::try_from(ny / len_sq - py).ok()?,
+        ))
+    }
+
+    /// Interpret this vector as axial hex coordinates `(q, r)` and convert them to cube
+    /// coordinates `(q, r, -q - r)`, which always satisfy `x + y + z == 0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `-q - r` does not fit in an `i32`, as for `(i32::MIN, 0)`.
+    #[inline]
+    pub fn axial_to_cube(&self) -> Vec3i {
+        let z = -i64::from(self.x) - i64::from(self.y);
+        Vec3i::new(self.x, self.y, i32::try_from(z).expect("Cube z coordinate overflows i32"))
+    }
+
+    /// The area of the rectangle with this vector as its extent, i.e. `x * y`.
+    ///
+    /// This overflows like any other `i32` multiplication, panicking in debug builds. Use
+    /// `area_wide` if the extent may be large.
+    #[inline]
+    pub fn area(&self) -> i32 {
+        self.x * self.y
+    }
+
+    /// Like `area`, but computed in `i64`, which cannot overflow.
+    #[inline]
+    pub fn area_wide(&self) -> i64 {
+        i64::from(self.x) * i64::from(self.y)
+    }
+
+    /// The centroid (mean) of `points`, rounded to the nearest integer with ties rounded away
+    /// from zero. The sum is accumulated in `i64`, so it cannot overflow for any realistic
+    /// number of points.
+    ///
+    /// Returns `Vec2i::zero()` for an empty slice.
+    #[inline]
+    pub fn centroid(points: &[Vec2i]) -> Vec2i {
+        if points.is_empty() {
+            return Vec2i::zero();
+        }
+        let mut sum = [0i64; 2];
+        for p in points {
+            sum[0] += i64::from(p.x);
+            sum[1] += i64::from(p.y);
+        }
+        let count = points.len() as i64;
+        Vec2i { x: div_round(sum[0], count) as i32, y: div_round(sum[1], count) as i32 }
+    }
+
+    /// The quadrant (`0..4`) of `center` that this point falls into, as used when descending
+    /// a quadtree. Bit 0 is set if `x >= center.x` and bit 1 if `y >= center.y`, so points lying
+    /// exactly on one of the center's axes are counted on the positive side.
+    #[inline]
+    pub fn quadrant(&self, center: Vec2i) -> u8 {
+        (self.x >= center.x) as u8 | ((self.y >= center.y) as u8) << 1
+    }
+
+    /// The direction whose offset is exactly this vector, or `None` if this is not a unit
+    /// vector along an axis.
+    #[inline]
+    pub fn to_direction4(&self) -> Option<Direction4> {
+        Direction4::ALL.iter().copied().find(|dir| dir.to_offset() == *self)
+    }
+
+    /// The direction whose offset is exactly this vector, or `None` if this is not one of the
+    /// eight offsets with each component `-1`, `0` or `1`.
+    #[inline]
+    pub fn to_direction8(&self) -> Option<Direction8> {
+        Direction8::ALL.iter().copied().find(|dir| dir.to_offset() == *self)
+    }
+
+    /// Clamp each component into the inclusive bounds of `aabb`, giving the closest point
+    /// inside it.
+    #[inline]
+    pub fn clamp_to_aabb(&self, aabb: &IntAabb2) -> Self {
+        self.clamped(aabb.min, aabb.max)
+    }
+
+    /// Wrap this signed coordinate into an unsigned grid of the given size, as when sampling
+    /// a repeating texture, so that `-1` maps to `size - 1` and so on. The wrapping is done in
+    /// `i64`, so any `size` up to `u32::MAX` is handled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either component of `size` is zero.
+    #[inline]
+    pub fn sample_index(&self, size: Vec2u) -> Vec2u {
+        let wrap = |c: i32, size: u32| i64::from(c).rem_euclid(i64::from(size)) as u32;
+        Vec2u::new(wrap(self.x, size.x), wrap(self.y, size.y))
+    }
+}
+
+macro_rules! vec3i {
+    ($(($v2t:ident, $n:ident, $v4t:ident) => $t:ident),+) => {
+        /// A set of three coordinates which may be interpreted as a point or vector in 3d space,
+        /// or as a homogeneous 2d vector or point.
         ///
         /// Generally this distinction between a point and vector is more of a pain than it is worth
         /// to distinguish on a type level, however when converting to and from homogeneous
         /// coordinates it is quite important.
-        $(#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        $(#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
         #[repr(C)]
         pub struct $n {
             pub x: $t,
             pub y: $t,
             pub z: $t,
-            pub w: $t,
         }
 
         impl $n {
             #[inline]
-            pub fn new<T: Into<$t>>(x: T, y: T, z: T, w: T) -> Self {
-                $n { x: x.into(), y: y.into(), z: z.into(), w: w.into() }
+            pub fn new(x: $t, y: $t, z: $t) -> Self {
+                $n { x, y, z }
             }
 
             #[inline]
-            pub fn broadcast<T: Into<$t> + Copy>(val: T) -> Self {
-                Self::new(val, val, val, val)
+            pub fn broadcast(val: $t) -> Self {
+                Self::new(val, val, val)
             }
 
             #[inline]
             pub fn unit_x() -> Self {
-                $n{ x: 1, y: 0, z: 0, w: 0 }
+                $n{ x: 1, y: 0, z: 0 }
             }
 
             #[inline]
             pub fn unit_y() -> Self {
-                $n{ x: 0, y: 1, z: 0, w: 0 }
+                $n{ x: 0, y: 1, z: 0 }
             }
 
             #[inline]
             pub fn unit_z() -> Self {
-                $n{ x: 0, y: 0, z: 1, w: 0 }
+                $n{ x: 0, y: 0, z: 1 }
             }
 
             #[inline]
-            pub fn unit_w() -> Self {
-                $n{ x: 0, y: 0, z: 0, w: 1 }
+            pub fn cross(&self, other: $n) -> Self {
+                $n::new(
+                    self.y.mul_add(other.z, -(self.z as i32) as $t * other.y),
+                    self.z.mul_add(other.x, -(self.x as i32) as $t * other.z),
+                    self.x.mul_add(other.y, -(self.y as i32) as $t * other.x),
+                )
+            }
+
+            /// Create a homogeneous 3d *point* from this vector interpreted as a point,
+            /// meaning the homogeneous component will start with a value of 1.
+            #[inline]
+            pub fn into_homogeneous_point(self) -> $v4t {
+                $v4t { x: self.x, y: self.y, z: self.z, w: 1 }
+            }
+
+            /// Create a homogeneous 3d *vector* from this vector,
+            /// meaning the homogeneous component will always have a value of 0.
+            #[inline]
+            pub fn into_homogeneous_vector(self) -> $v4t {
+                $v4t { x: self.x, y: self.y, z: self.z, w: 0 }
+            }
+
+            /// Create a homogeneous 3d *direction* from this vector, with a homogeneous component
+            /// of 0. This is an alias of `into_homogeneous_vector`, named to make clear that the
+            /// result is unaffected by translation.
+            #[inline]
+            pub fn into_homogeneous_direction(self) -> $v4t {
+                self.into_homogeneous_vector()
+            }
+
+            /// Create a 3d point from a homogeneous 3d *point*, performing
+            /// division by the homogeneous component. This should not be used
+            /// for homogeneous 3d *vectors*, which will have 0 as their
+            /// homogeneous component.
+            #[inline]
+            pub fn from_homogeneous_point(v: $v4t) -> Self {
+                Self { x: v.x / v.w, y: v.y / v.w, z: v.z / v.w }
+            }
+
+            /// Create a 3d vector from homogeneous 2d *vector*, which simply
+            /// discards the homogeneous component.
+            #[inline]
+            pub fn from_homogeneous_vector(v: $v4t) -> Self {
+                v.into()
             }
 
+
             #[inline]
             pub fn dot(&self, other: $n) -> $t {
-                self.x.mul_add(other.x, self.y.mul_add(other.y, self.z.mul_add(other.z, self.w * other.w)))
+                self.x.mul_add(other.x, self.y.mul_add(other.y, self.z * other.z))
             }
 
             #[inline]
@@ -970,12 +1982,12 @@ macro_rules! vec4i {
 
             #[inline]
             pub fn mag(&self) -> $t {
-                (self.mag_sq() as f64).sqrt() as $t
+                isqrt_u128(self.mag_sq().max(0) as u128) as $t
             }
 
             #[inline]
             pub fn mag_sq(&self) -> $t {
-                self.x.mul_add(self.x, self.y.mul_add(self.y, self.z.mul_add(self.z, self.w * self.w)))
+                self.x.mul_add(self.x, self.y.mul_add(self.y, self.z * self.z))
             }
 
             #[inline]
@@ -984,16 +1996,19 @@ macro_rules! vec4i {
                     self.x.mul_add(mul.x, add.x),
                     self.y.mul_add(mul.y, add.y),
                     self.z.mul_add(mul.z, add.z),
-                    self.w.mul_add(mul.w, add.w),
                 )
             }
 
+            #[inline]
+            pub fn abs(&self) -> Self {
+                Self::new(self.x, self.y, self.z)
+            }
+
             #[inline]
             pub fn clamp(&mut self, min: Self, max: Self) {
                 self.x = self.x.max(min.x).min(max.x);
                 self.y = self.y.max(min.y).min(max.y);
                 self.z = self.z.max(min.z).min(max.z);
-                self.w = self.w.max(min.w).min(max.w);
             }
 
             #[inline]
@@ -1009,8 +2024,7 @@ macro_rules! vec4i {
                 $n::new(
                     f(self.x),
                     f(self.y),
-                    f(self.z),
-                    f(self.w),
+                    f(self.z)
                 )
             }
 
@@ -1021,7 +2035,6 @@ macro_rules! vec4i {
                 self.x = f(self.x);
                 self.y = f(self.y);
                 self.z = f(self.z);
-                self.w = f(self.w);
             }
 
             #[inline]
@@ -1029,7 +2042,6 @@ macro_rules! vec4i {
                 self.x = self.x.max(other.x);
                 self.y = self.y.max(other.y);
                 self.z = self.z.max(other.z);
-                self.w = self.w.max(other.w);
                 self
             }
 
@@ -1038,327 +2050,2630 @@ macro_rules! vec4i {
                 self.x = self.x.min(other.x);
                 self.y = self.y.min(other.y);
                 self.z = self.z.min(other.z);
-                self.w = self.w.min(other.w);
                 self
             }
 
             #[inline]
             pub fn component_max(&self) -> $t {
-                self.x.max(self.y).max(self.z).max(self.w)
+                self.x.max(self.y).max(self.z)
             }
 
             #[inline]
             pub fn component_min(&self) -> $t {
-                self.x.min(self.y).min(self.z).min(self.w)
+                self.x.min(self.y).min(self.z)
             }
 
-            #[inline]
-            pub fn zero() -> Self {
-                Self::broadcast(0 as $t)
+            /// Split this coordinate into the index of the chunk containing it and the local
+            /// offset within that chunk. Euclidean division is used so that negative coordinates
+            /// land in the correct chunk with a local offset in `0..chunk_size`.
+            #[inline]
+            pub fn split_chunk(&self, chunk_size: Self) -> (Self, Self) {
+                (
+                    $n::new(
+                        self.x.div_euclid(chunk_size.x),
+                        self.y.div_euclid(chunk_size.y),
+                        self.z.div_euclid(chunk_size.z),
+                    ),
+                    $n::new(
+                        self.x.rem_euclid(chunk_size.x),
+                        self.y.rem_euclid(chunk_size.y),
+                        self.z.rem_euclid(chunk_size.z),
+                    ),
+                )
             }
 
+            /// Like `min_by_component`, but also returns a mask which is set for each component
+            /// taken from `other`. On ties the component from `self` is kept.
             #[inline]
-            pub fn one() -> Self {
-                Self::broadcast(1 as $t)
+            pub fn min_by_component_tracked(&self, other: Self) -> (Self, BVec3) {
+                let mask = BVec3::new(other.x < self.x, other.y < self.y, other.z < self.z);
+                (self.min_by_component(other), mask)
             }
 
+            /// Like `max_by_component`, but also returns a mask which is set for each component
+            /// taken from `other`. On ties the component from `self` is kept.
             #[inline]
-            pub fn xy(&self) -> $v2t {
-                $v2t::new(self.x, self.y)
+            pub fn max_by_component_tracked(&self, other: Self) -> (Self, BVec3) {
+                let mask = BVec3::new(other.x > self.x, other.y > self.y, other.z > self.z);
+                (self.max_by_component(other), mask)
             }
 
-             #[inline]
-            pub fn xyz(&self) -> $v3t {
-                $v3t::new(self.x, self.y, self.z)
+            /// A fast hash of the coordinates, intended for custom spatial hash maps where the
+            /// default `Hash` implementation and hasher are too slow.
+            ///
+            /// Each component's 32 bits are folded in using the FxHash step (rotate left by 5, xor,
+            /// multiply by `0x517cc1b727220a95`) and the result is finished with the MurmurHash3
+            /// `fmix64` finalizer. The hash is deterministic across platforms and runs.
+            #[inline]
+            pub fn spatial_hash(&self) -> u64 {
+                let mut hash = 0u64;
+                for &c in self.as_slice() {
+                    hash = (hash.rotate_left(5) ^ (c as u64 & 0xffff_ffff)).wrapping_mul(SPATIAL_HASH_SEED);
+                }
+                fmix64(hash)
+            }
+
+            /// Promote each component to `f64`, which represents every 32-bit integer exactly.
+            #[inline]
+            pub fn to_f64_array(&self) -> [f64; 3] {
+                [self.x as f64, self.y as f64, self.z as f64]
             }
 
+            /// Convert each component to `f32`, which rounds integers with a magnitude above `2^24`.
+            #[inline]
+            pub fn to_f32_array(&self) -> [f32; 3] {
+                [self.x as f32, self.y as f32, self.z as f32]
+            }
 
+            /// Round each component down to a multiple of the corresponding component of `multiple`,
+            /// using Euclidean division so that negative values round towards negative infinity.
+            /// Components with a multiple of zero are left unchanged.
             #[inline]
-            pub fn layout() -> alloc::alloc::Layout {
-                alloc::alloc::Layout::from_size_align(std::mem::size_of::<Self>(), std::mem::align_of::<$t>()).unwrap()
+            pub fn align_down(&self, multiple: Self) -> Self {
+                let align = |c: $t, m: $t| if m == 0 { c } else { c - c.rem_euclid(m) };
+                $n::new(
+                    align(self.x, multiple.x),
+                    align(self.y, multiple.y),
+                    align(self.z, multiple.z),
+                )
+            }
+
+            /// Round each component up to a multiple of the corresponding component of `multiple`,
+            /// using Euclidean division so that negative values round towards positive infinity.
+            /// Components with a multiple of zero are left unchanged.
+            #[inline]
+            pub fn align_up(&self, multiple: Self) -> Self {
+                let align = |c: $t, m: $t| {
+                    let r = if m == 0 { 0 } else { c.rem_euclid(m) };
+                    if r == 0 {
+                        c
+                    } else if m > 0 {
+                        c + (m - r)
+                    } else {
+                        c - r - m
+                    }
+                };
+                $n::new(
+                    align(self.x, multiple.x),
+                    align(self.y, multiple.y),
+                    align(self.z, multiple.z),
+                )
             }
 
+            /// The components of this vector as little-endian bytes, in `x, y, ...` order.
             #[inline]
-            pub fn as_slice(&self) -> &[$t] {
-                // This is safe because we are statically bounding our slices to the size of these
-                // vectors
-                unsafe {
-                    std::slice::from_raw_parts(self as *const $n as *const $t, 4)
+            pub fn to_le_bytes(&self) -> [u8; 3 * core::mem::size_of::<$t>()] {
+                let mut bytes = [0; 3 * core::mem::size_of::<$t>()];
+                for (chunk, c) in bytes.chunks_exact_mut(core::mem::size_of::<$t>()).zip(self.as_slice()) {
+                    chunk.copy_from_slice(&c.to_le_bytes());
                 }
+                bytes
             }
 
+            /// Create a vector from components stored as little-endian bytes, in `x, y, ...` order.
             #[inline]
-            pub fn as_byte_slice(&self) -> &[u8] {
-                // This is safe because we are statically bounding our slices to the size of these
-                // vectors
-                unsafe {
-                    std::slice::from_raw_parts(self as *const $n as *const u8, 4 * std::mem::size_of::<$t>())
+            pub fn from_le_bytes(bytes: [u8; 3 * core::mem::size_of::<$t>()]) -> Self {
+                let mut v = Self::zero();
+                for (c, chunk) in v.as_mut_slice().iter_mut().zip(bytes.chunks_exact(core::mem::size_of::<$t>())) {
+                    let mut comp = [0; core::mem::size_of::<$t>()];
+                    comp.copy_from_slice(chunk);
+                    *c = $t::from_le_bytes(comp);
                 }
+                v
             }
 
+            /// The components of this vector as big-endian bytes, in `x, y, ...` order.
             #[inline]
-            pub fn as_mut_slice(&mut self) -> &mut [$t] {
-                // This is safe because we are statically bounding our slices to the size of these
-                // vectors
-                unsafe {
-                    std::slice::from_raw_parts_mut(self as *mut $n as *mut $t, 4)
+            pub fn to_be_bytes(&self) -> [u8; 3 * core::mem::size_of::<$t>()] {
+                let mut bytes = [0; 3 * core::mem::size_of::<$t>()];
+                for (chunk, c) in bytes.chunks_exact_mut(core::mem::size_of::<$t>()).zip(self.as_slice()) {
+                    chunk.copy_from_slice(&c.to_be_bytes());
                 }
+                bytes
             }
 
+            /// Create a vector from components stored as big-endian bytes, in `x, y, ...` order.
             #[inline]
-            pub fn as_mut_byte_slice(&mut self) -> &mut [u8] {
-                // This is safe because we are statically bounding our slices to the size of these
-                // vectors
-                unsafe {
-                    std::slice::from_raw_parts_mut(self as *mut $n as *mut u8, 4 * std::mem::size_of::<$t>())
+            pub fn from_be_bytes(bytes: [u8; 3 * core::mem::size_of::<$t>()]) -> Self {
+                let mut v = Self::zero();
+                for (c, chunk) in v.as_mut_slice().iter_mut().zip(bytes.chunks_exact(core::mem::size_of::<$t>())) {
+                    let mut comp = [0; core::mem::size_of::<$t>()];
+                    comp.copy_from_slice(chunk);
+                    *c = $t::from_be_bytes(comp);
                 }
+                v
             }
 
-            /// Returns a constant unsafe pointer to the underlying data in the underlying type.
-            /// This function is safe because all types here are repr(C) and can be represented
-            /// as their underlying type.
-            ///
-            /// # Safety
-            ///
-            /// It is up to the caller to correctly use this pointer and its bounds.
+            /// Cyclically shift the components one place towards `x`, so `(x, y, z)` becomes `(y, z, x)`.
             #[inline]
-            pub fn as_ptr(&self) -> *const $t {
-                 self as *const $n as *const $t
+            pub fn rotate_components(&self) -> Self {
+                $n::new(self.y, self.z, self.x)
             }
 
-            /// Returns a mutable unsafe pointer to the underlying data in the underlying type.
-            /// This function is safe because all types here are repr(C) and can be represented
-            /// as their underlying type.
+            /// Cyclically shift the components one place away from `x`, so `(x, y, z)` becomes `(z, x, y)`.
+            #[inline]
+            pub fn rotate_components_rev(&self) -> Self {
+                $n::new(self.z, self.x, self.y)
+            }
+
+            /// Cyclically shift the components `n` places towards `x`, so `rotate_components_by(1)`
+            /// is the same as `rotate_components`. `n` is taken modulo the dimension.
+            #[inline]
+            pub fn rotate_components_by(&self, n: usize) -> Self {
+                let mut comps: [$t; 3] = (*self).into();
+                comps.rotate_left(n % 3);
+                comps.into()
+            }
+
+            /// The component along the given axis (0 for `x`, 1 for `y`, ...), or `None` if the axis
+            /// is out of range for this vector.
+            #[inline]
+            pub fn component(&self, axis: usize) -> Option<$t> {
+                self.as_slice().get(axis).copied()
+            }
+
+            /// Returns a copy of this vector with the component along the given axis replaced by `value`.
             ///
-            /// # Safety
+            /// # Panics
             ///
-            /// It is up to the caller to correctly use this pointer and its bounds.
+            /// Panics if the axis is out of range for this vector.
             #[inline]
-            pub fn as_mut_ptr(&mut self) -> *mut $t {
-                self as *mut $n as *mut $t
+            pub fn with_component(mut self, axis: usize, value: $t) -> Self {
+                match self.as_mut_slice().get_mut(axis) {
+                    Some(c) => *c = value,
+                    None => panic!("Invalid axis {} for vector of type: {}", axis, core::any::type_name::<$n>()),
+                }
+                self
             }
-        }
 
-        impl From<[$t; 4]> for $n {
+            /// The components reordered as `(y, z, x)`, the same as `rotate_components`.
             #[inline]
-            fn from(comps: [$t; 4]) -> Self {
-                Self::new(comps[0], comps[1], comps[2], comps[3])
+            pub fn yzx(&self) -> Self {
+                self.rotate_components()
             }
-        }
-        impl Into<[$t; 4]> for $n {
+
+            /// The components reordered as `(z, x, y)`, the same as `rotate_components_rev`.
             #[inline]
-            fn into(self) -> [$t; 4] {
-                [self.x, self.y, self.z, self.w]
+            pub fn zxy(&self) -> Self {
+                self.rotate_components_rev()
             }
-        }
 
-        impl From<&[$t; 4]> for $n {
+            /// Swap the `y` and `z` components, converting between y-up and z-up coordinate systems.
+            /// Note that this also flips handedness.
             #[inline]
-            fn from(comps: &[$t; 4]) -> Self {
-                Self::from(*comps)
+            pub fn with_yz_swapped(&self) -> Self {
+                $n::new(self.x, self.z, self.y)
             }
-        }
 
-        impl From<&mut [$t; 4]> for $n {
+            /// Reorder the axes so that component `i` of the result is component `axes[i]` of `self`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `axes` is not a permutation of `[0, 1, 2]`.
             #[inline]
-            fn from(comps: &mut [$t; 4]) -> Self {
-                Self::from(*comps)
+            pub fn permute_axes(&self, axes: [usize; 3]) -> Self {
+                let mut seen = [false; 3];
+                for &axis in axes.iter() {
+                    assert!(axis < 3 && !seen[axis], "{:?} is not a permutation of the axes of a 3d vector", axes);
+                    seen[axis] = true;
+                }
+                $n::new(self[axes[0]], self[axes[1]], self[axes[2]])
             }
-        }
 
-        impl From<($t, $t, $t, $t)> for $n {
+            /// Like `from_homogeneous_point`, but rounds each component to the nearest integer after
+            /// dividing by the homogeneous component instead of truncating towards zero. Ties are
+            /// rounded away from zero.
             #[inline]
-            fn from(comps: ($t, $t, $t, $t)) -> Self {
-                Self::new(comps.0, comps.1, comps.2, comps.3)
+            pub fn from_homogeneous_point_rounded(v: $v4t) -> Self {
+                let round = |c: $t| div_round(c as i64, v.w as i64) as $t;
+                Self::new(round(v.x), round(v.y), round(v.z))
             }
-        }
 
-        impl From<&($t, $t, $t, $t)> for $n {
+            /// The smallest and largest components, `(component_min, component_max)`, computed in a
+            /// single pass.
             #[inline]
-            fn from(comps: &($t, $t, $t, $t)) -> Self {
-                Self::from(*comps)
+            pub fn component_min_max(&self) -> ($t, $t) {
+                let (mut min, mut max) = (self.x, self.x);
+                for &c in &[self.y, self.z] {
+                    if c < min {
+                        min = c;
+                    } else if c > max {
+                        max = c;
+                    }
+                }
+                (min, max)
             }
-        }
 
-        impl From<$n> for ($t, $t, $t, $t) {
+            /// Like `mul_add`, but returns `None` if any component of the result does not fit in the
+            /// element type. The multiplication and addition are computed in `i128`, so an
+            /// intermediate product which overflows is fine as long as the final sum fits.
             #[inline]
-            fn from(v: $n) -> Self {
-                (v.x, v.y, v.z, v.w)
+            pub fn checked_mul_add(&self, mul: Self, add: Self) -> Option<Self> {
+                let fma = |c: $t, m: $t, a: $t| $t::try_from(c as i128 * m as i128 + a as i128).ok();
+                Some($n { x: fma(self.x, mul.x, add.x)?, y: fma(self.y, mul.y, add.y)?, z: fma(self.z, mul.z, add.z)? })
             }
-        }
 
-        impl Add for $n {
-            type Output = Self;
+            /// Returns an iterator over mutable references to the components of this vector, in order.
             #[inline]
-            fn add(self, rhs: $n) -> Self {
-                $n::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z, self.w + rhs.w)
+            pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, $t> {
+                self.as_mut_slice().iter_mut()
             }
-        }
 
-        impl AddAssign for $n {
+            /// Returns the per-component median of `a`, `b` and `c`.
+            ///
+            /// Clamping `v` between `lo` and `hi` with `lo <= hi` is equivalent to `median(v, lo, hi)`.
             #[inline]
-            fn add_assign(&mut self, rhs: $n) {
-                self.x += rhs.x;
-                self.y += rhs.y;
-                self.z += rhs.z;
-                self.w += rhs.w;
+            pub fn median(a: Self, b: Self, c: Self) -> Self {
+                a.min_by_component(b)
+                    .max_by_component(a.max_by_component(b).min_by_component(c))
             }
-        }
 
-        impl Sub for $n {
-            type Output = Self;
+            /// Convert this vector to another vector type, failing if any component is out of range
+            /// for the target, e.g. `Vec3i::new(1, 2, 3).try_cast::<Vec3u>()`.
             #[inline]
-            fn sub(self, rhs: $n) -> Self {
-                $n::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z, self.w - rhs.w)
+            pub fn try_cast<T: TryFrom<Self>>(&self) -> Result<T, T::Error> {
+                T::try_from(*self)
             }
-        }
 
-        impl SubAssign for $n {
+            /// Compare two vectors under the component-wise partial order.
+            ///
+            /// Returns `Less` if every component of `self` is `<=` the matching component of `other`
+            /// and at least one is strictly less, `Greater` in the symmetric case, `Equal` if all
+            /// components are equal and `None` if the vectors are incomparable.
+            #[inline]
+            pub fn partial_cmp_componentwise(&self, other: Self) -> Option<core::cmp::Ordering> {
+                let mut less = false;
+                let mut greater = false;
+                for (a, b) in self.as_slice().iter().zip(other.as_slice()) {
+                    less |= a < b;
+                    greater |= a > b;
+                }
+                match (less, greater) {
+                    (false, false) => Some(core::cmp::Ordering::Equal),
+                    (true, false) => Some(core::cmp::Ordering::Less),
+                    (false, true) => Some(core::cmp::Ordering::Greater),
+                    (true, true) => None,
+                }
+            }
+
+            /// Compute the weighted sum `Σ wᵢ·vᵢ` of the given `(weight, vector)` terms.
+            ///
+            /// The sum is accumulated in `i128`, so intermediate products and partial sums never
+            /// overflow; only the final result must fit in the element type.
+            ///
+            /// # Panics
+            ///
+            /// Panics if a component of the result does not fit in the element type.
+            #[inline]
+            pub fn weighted_sum(terms: &[($t, Self)]) -> Self {
+                let mut acc = [0i128; 3];
+                for &(weight, v) in terms {
+                    for (a, &c) in acc.iter_mut().zip(v.as_slice()) {
+                        *a += weight as i128 * c as i128;
+                    }
+                }
+                let mut sum = Self::zero();
+                for (c, a) in sum.iter_mut().zip(acc.iter()) {
+                    *c = $t::try_from(*a).expect("Weighted sum component overflows the element type");
+                }
+                sum
+            }
+
+            /// Count the components for which `pred` returns true.
             #[inline]
-            fn sub_assign(&mut self, rhs: $n) {
-                self.x -= rhs.x;
-                self.y -= rhs.y;
-                self.z -= rhs.z;
-                self.w -= rhs.w;
+            pub fn count_matching<F>(&self, pred: F) -> usize
+                where F: Fn($t) -> bool
+            {
+                self.as_slice().iter().filter(|&&c| pred(c)).count()
             }
-        }
 
-        impl Mul for $n {
-            type Output = Self;
+            /// The squared Euclidean distance between this point and `other`, computed in `u128`
+            /// so that it cannot overflow.
             #[inline]
-            fn mul(self, rhs: $n) -> Self {
-                $n::new(self.x * rhs.x, self.y * rhs.y, self.z * rhs.z, self.w * rhs. w)
+            pub fn distance_sq_to(&self, other: Self) -> u128 {
+                self.as_slice()
+                    .iter()
+                    .zip(other.as_slice())
+                    .map(|(&a, &b)| {
+                        let d = (a as i64 - b as i64).unsigned_abs() as u128;
+                        d * d
+                    })
+                    .sum()
             }
-        }
 
-        impl Mul<$n> for $t {
-            type Output = $n;
+            /// Returns a comparator ordering points by their squared distance to `reference`, for use
+            /// with `sort_by` and friends.
             #[inline]
-            fn mul(self, rhs: $n) -> $n {
-                $n::new(self * rhs.x, self * rhs.y, self * rhs.z, self * rhs.w)
+            pub fn cmp_by_distance_sq(reference: Self) -> impl Fn(&Self, &Self) -> core::cmp::Ordering {
+                move |a, b| a.distance_sq_to(reference).cmp(&b.distance_sq_to(reference))
             }
-        }
 
-        impl Mul<$t> for $n {
-            type Output = $n;
+            /// Returns true if every component lies in the half-open range `min..max`, i.e. is at
+            /// least the matching component of `min` and less than that of `max`.
             #[inline]
-            fn mul(self, rhs: $t) -> $n {
-                $n::new(self.x * rhs, self.y * rhs, self.z * rhs, self.w * rhs)
+            pub fn in_bounds(&self, min: Self, max: Self) -> bool {
+                self.as_slice()
+                    .iter()
+                    .zip(min.as_slice().iter().zip(max.as_slice()))
+                    .all(|(c, (lo, hi))| lo <= c && c < hi)
             }
-        }
 
-        impl MulAssign for $n {
+            /// Like `mul_add`, but with the same scalar multiplier for every component, i.e.
+            /// `self * mul + add`.
             #[inline]
-            fn mul_assign(&mut self, rhs: $n) {
-                self.x *= rhs.x;
-                self.y *= rhs.y;
-                self.z *= rhs.z;
-                self.w *= rhs.w;
+            pub fn mul_add_scalar(&self, mul: $t, add: $n) -> Self {
+                self.mul_add($n::broadcast(mul), add)
             }
-        }
 
-        impl MulAssign<$t> for $n {
+            /// The component at index `i` taken modulo the dimension, so that the components are
+            /// treated as a cyclic buffer and e.g. `-1` returns the last component.
             #[inline]
-            fn mul_assign(&mut self, rhs: $t) {
-                self.x *= rhs;
-                self.y *= rhs;
-                self.z *= rhs;
-                self.w *= rhs;
+            pub fn wrapping_component(&self, i: isize) -> $t {
+                self.as_slice()[i.rem_euclid(3) as usize]
             }
-        }
 
-        impl Div for $n {
-            type Output = Self;
+            /// Like `from_homogeneous_point`, but returns `None` instead of panicking when the
+            /// homogeneous component is zero, i.e. when `v` is a homogeneous 3d *vector* rather than
+            /// a point, or when the division overflows.
             #[inline]
-            fn div(self, rhs: $n) -> Self {
-                $n::new(self.x / rhs.x, self.y / rhs.y, self.z / rhs.z, self.w / rhs.w)
+            pub fn checked_from_homogeneous_point(v: $v4t) -> Option<Self> {
+                Some(Self { x: v.x.checked_div(v.w)?, y: v.y.checked_div(v.w)?, z: v.z.checked_div(v.w)? })
             }
-        }
 
-        impl Div<$t> for $n {
-            type Output = $n;
+            /// Returns an iterator over the components of this vector, in order. The iterator is
+            /// double-ended, so `v.iter().rev()` walks the components from last to first.
             #[inline]
-            fn div(self, rhs: $t) -> $n {
-                $n::new(self.x / rhs, self.y / rhs, self.z / rhs, self.w / rhs)
+            pub fn iter(&self) -> core::slice::Iter<'_, $t> {
+                self.as_slice().iter()
             }
-        }
 
-        impl DivAssign for $n {
+            /// Returns an iterator over the components of this vector from last to first.
             #[inline]
-            fn div_assign(&mut self, rhs: $n) {
-                self.x /= rhs.x;
-                self.y /= rhs.y;
-                self.z /= rhs.z;
-                self.w /= rhs.w;
+            pub fn iter_rev(&self) -> core::iter::Rev<core::slice::Iter<'_, $t>> {
+                self.iter().rev()
             }
-        }
 
-        impl DivAssign<$t> for $n {
+            /// Treating this vector as a homogeneous 2d point, divide the other components by the
+            /// homogeneous component `z`, giving the equivalent point with `z == 1`.
+            ///
+            /// Returns `None` if `z` is zero (the vector is a homogeneous 2d *vector*, not a point)
+            /// or does not divide every other component exactly, in which case the point has no
+            /// integer representation with `z == 1`. The division overflowing also returns `None`.
             #[inline]
-            fn div_assign(&mut self, rhs: $t) {
-                self.x /= rhs;
-                self.y /= rhs;
-                self.z /= rhs;
-                self.w /= rhs;
+            pub fn homogenize(&self) -> Option<Self> {
+                let div = |c: $t| match c.checked_rem(self.z) {
+                    Some(0) => c.checked_div(self.z),
+                    _ => None,
+                };
+                Some(Self { x: div(self.x)?, y: div(self.y)?, z: 1 })
             }
-        }
 
-        impl Index<usize> for $n {
-            type Output = $t;
+            /// The sum of all components.
+            #[inline]
+            pub fn component_sum(&self) -> $t {
+                self.x + self.y + self.z
+            }
 
-            fn index(&self, index: usize) -> &Self::Output {
-                match index {
-                    0 => &self.x,
-                    1 => &self.y,
-                    2 => &self.z,
-                    3 => &self.w,
-                    _ => panic!("Invalid for vector of type: {}", std::any::type_name::<$n>()),
-                }
+            /// Returns an iterator over pairs of matching components of `self` and `other`, in order.
+            #[inline]
+            pub fn zip(&self, other: Self) -> impl Iterator<Item = ($t, $t)> {
+                let this = *self;
+                (0..3).map(move |i| (this[i], other[i]))
             }
-        }
 
-        impl IndexMut<usize> for $n {
-            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-                match index {
-                    0 => &mut self.x,
-                    1 => &mut self.y,
-                    2 => &mut self.z,
-                    3 => &mut self.w,
-                    _ => panic!("Invalid for vector of type: {}", std::any::type_name::<$n>()),
+            /// Interpolate `percent` percent of the way from `self` to `end`, rounding each component
+            /// to the nearest integer with ties rounded away from `self`. `percent` is clamped to
+            /// `0..=100`, and the math is done in `i64` so it cannot overflow.
+            #[inline]
+            pub fn lerp_percent(&self, end: Self, percent: $t) -> Self {
+                let percent = i64::from(percent.clamp(0, 100));
+                let mut out = *self;
+                for (c, &e) in out.iter_mut().zip(end.as_slice()) {
+                    let start = i64::from(*c);
+                    *c = (start + div_round((i64::from(e) - start) * percent, 100)) as $t;
                 }
+                out
             }
-        }
-        )+
-    }
-}
 
-vec4i!(Vec4u, Vec2u, Vec3u => u32);
-vec4i!(Vec4i, Vec2i, Vec3i => i32);
+            /// Combine each pair of matching components of `self` and `other` with `f`, collecting
+            /// the results in an array. Unlike `map`, the output may be of any type.
+            #[inline]
+            pub fn map2_to<U, F>(&self, other: Self, f: F) -> [U; 3]
+                where F: Fn($t, $t) -> U
+            {
+                [f(self.x, other.x), f(self.y, other.y), f(self.z, other.z)]
+            }
 
-impl From<Vec3u> for Vec2u {
-    #[inline]
-    fn from(vec: Vec3u) -> Self {
-        Self {
-            x: vec.x,
-            y: vec.y,
-        }
-    }
-}
+            /// The Manhattan distance between `self` and `other` on a grid of size `extent` which
+            /// wraps around at its edges, like the surface of a torus. Along each axis the shorter of
+            /// the direct and the wrapped-around difference is taken, and these are summed.
+            ///
+            /// Both points should lie within `0..extent`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if any component of `extent` is not positive, or if the distance does not fit
+            /// in the element type.
+            #[inline]
+            pub fn toroidal_distance(&self, other: Self, extent: Self) -> $t {
+                let mut sum = 0i64;
+                for (i, &e) in extent.as_slice().iter().enumerate() {
+                    assert!(e > 0, "Toroidal extent must be positive on every axis");
+                    let e = i64::from(e);
+                    let d = (i64::from(self[i]) - i64::from(other[i])).rem_euclid(e);
+                    sum += d.min(e - d);
+                }
+                $t::try_from(sum).expect("Toroidal distance overflows the element type")
+            }
 
+            /// Like `component_sum`, but returns `None` if the sum overflows.
+            #[inline]
+            pub fn checked_component_sum(&self) -> Option<$t> {
+                self.iter().try_fold(0 as $t, |acc, &c| acc.checked_add(c))
+            }
 
-impl From<Vec3u> for Vec4u {
-    #[inline]
-    fn from(vec: Vec3u) -> Self {
-        Self {
-            x: vec.x,
-            y: vec.y,
-            z: vec.z,
-            w: 0,
-        }
-    }
-}
+            /// The product of all components, or `None` if it overflows. This is useful for sizing a
+            /// buffer from an extent before allocating it.
+            #[inline]
+            pub fn checked_component_product(&self) -> Option<$t> {
+                self.iter().try_fold(1 as $t, |acc, &c| acc.checked_mul(c))
+            }
 
-impl From<Vec4u> for Vec3u {
+            /// Convert this vector into an array whose length is a const generic parameter, for use
+            /// with code which is generic over the array length. Using a length other than the
+            /// vector's dimension is a compile-time error.
+            #[inline]
+            pub fn into_const_array<const N: usize>(self) -> [$t; N] {
+                let () = AssertLen::<N, 3>::OK;
+                let mut comps = [0 as $t; N];
+                comps.copy_from_slice(self.as_slice());
+                comps
+            }
+
+            /// Raise each component to the power `exp`, saturating at the bounds of the element
+            /// type instead of overflowing.
+            #[inline]
+            pub fn saturating_pow(self, exp: u32) -> Self {
+                $n { x: self.x.saturating_pow(exp), y: self.y.saturating_pow(exp), z: self.z.saturating_pow(exp) }
+            }
+
+            /// The component-wise greatest common divisor of `self` and `other`, which is always
+            /// non-negative. `gcd(0, n)` is `|n|`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the result does not fit in the element type, which is only possible when
+            /// both components are `0` or `i32::MIN` and at least one is `i32::MIN`.
+            #[inline]
+            pub fn gcd(&self, other: Self) -> Self {
+                self.zip_with_u64(other, gcd_u64, "GCD overflows the element type")
+            }
+
+            /// The component-wise least common multiple of `self` and `other`, which is always
+            /// non-negative. `lcm(0, n)` is `0`.
+            ///
+            /// # Panics
+            ///
+            /// The LCM is computed in `u64` and so never overflows internally, but this panics if
+            /// the result does not fit in the element type.
+            #[inline]
+            pub fn lcm(&self, other: Self) -> Self {
+                self.zip_with_u64(other, lcm_u64, "LCM overflows the element type")
+            }
+
+            /// Combine the absolute values of matching components with `f`, converting each result
+            /// back to the element type.
+            #[inline]
+            fn zip_with_u64(&self, other: Self, f: fn(u64, u64) -> u64, overflow: &str) -> Self {
+                let mut out = *self;
+                for (c, &o) in out.iter_mut().zip(other.as_slice()) {
+                    let r = f((*c as i64).unsigned_abs(), (o as i64).unsigned_abs());
+                    *c = $t::try_from(r).expect(overflow);
+                }
+                out
+            }
+
+            /// The unit vector along the given axis (0 for `x`, 1 for `y`, ...), for when the axis
+            /// is only known at runtime.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the axis is out of range for this vector, like `with_component`.
+            #[inline]
+            pub fn unit_axis(axis: usize) -> Self {
+                Self::zero().with_component(axis, 1)
+            }
+
+            /// The distance between `self` and `other` under the metric `M`. The per-axis
+            /// differences and the metric are computed in wider types, so only the final distance
+            /// needs to fit in the element type.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the distance does not fit in the element type.
+            #[inline]
+            pub fn distance<M: Metric>(&self, other: Self) -> $t {
+                let mut diffs = [0u64; 3];
+                for (d, (&a, &b)) in diffs.iter_mut().zip(self.as_slice().iter().zip(other.as_slice())) {
+                    *d = (a as i64 - b as i64).unsigned_abs();
+                }
+                $t::try_from(M::combine(&diffs)).expect("Distance overflows the element type")
+            }
+
+            /// Like `apply`, but also passes the index of each component (0 for `x`, 1 for `y`, ...)
+            /// to `f`.
+            #[inline]
+            pub fn apply_indexed<F>(&mut self, f: F)
+                where F: Fn(usize, $t) -> $t
+            {
+                for (i, c) in self.iter_mut().enumerate() {
+                    *c = f(i, *c);
+                }
+            }
+
+            /// Build a vector whose `i`th component is the component of `self` at `indices[i]`,
+            /// a swizzle chosen at runtime. Indices may repeat.
+            ///
+            /// # Panics
+            ///
+            /// Panics if any index is out of range for this vector, like indexing does.
+            #[inline]
+            pub fn gather(&self, indices: [usize; 3]) -> Self {
+                $n { x: self[indices[0]], y: self[indices[1]], z: self[indices[2]] }
+            }
+
+            /// Clamp each component between `min` and `max` like `Ord::clamp`.
+            ///
+            /// Unlike the lenient [`clamped`](Self::clamped), which silently prefers `max` when the
+            /// bounds are inverted, this enforces the same contract as `std`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `min` is greater than `max` on any axis.
+            #[inline]
+            pub fn clamp_ord(self, min: Self, max: Self) -> Self {
+                assert!(!(min.x > max.x || min.y > max.y || min.z > max.z), "clamp_ord: min must not exceed max on any axis");
+                self.clamped(min, max)
+            }
+
+            /// Scale each component by the fraction `num / den`, rounding to the nearest integer with
+            /// ties rounded away from zero. The product is computed in `i128` so it cannot overflow.
+            ///
+            /// Returns `None` if `den` is zero or a scaled component does not fit in the component type.
+            #[inline]
+            pub fn scale_rational(&self, num: $t, den: $t) -> Option<Self> {
+                if den == 0 {
+                    return None;
+                }
+                let mut out = *self;
+                for c in out.iter_mut() {
+                    let scaled = div_round_i128(i128::from(*c) * i128::from(num), i128::from(den));
+                    *c = <$t>::try_from(scaled).ok()?;
+                }
+                Some(out)
+            }
+
+            #[inline]
+            pub fn zero() -> Self {
+                Self::broadcast(0)
+            }
+
+            #[inline]
+            pub fn one() -> Self {
+                Self::broadcast(1)
+            }
+
+
+            #[inline]
+            pub fn xy(&self) -> $v2t {
+                $v2t::new(self.x, self.y)
+            }
+
+            #[inline]
+            pub fn xyzw(&self) -> $v4t {
+                $v4t::new(self.x, self.y, self.z, 0)
+            }
+
+            #[cfg(feature = "alloc")]
+            #[inline]
+            pub fn layout() -> alloc::alloc::Layout {
+                alloc::alloc::Layout::from_size_align(core::mem::size_of::<Self>(), core::mem::align_of::<$t>()).unwrap()
+            }
+
+            #[inline]
+            pub fn as_slice(&self) -> &[$t] {
+                // This is safe because we are statically bounding our slices to the size of these
+                // vectors
+                unsafe {
+                    core::slice::from_raw_parts(self as *const $n as *const $t, 3)
+                }
+            }
+
+            #[inline]
+            pub fn as_byte_slice(&self) -> &[u8] {
+                // This is safe because we are statically bounding our slices to the size of these
+                // vectors
+                unsafe {
+                    core::slice::from_raw_parts(self as *const $n as *const u8, 3 * core::mem::size_of::<$t>())
+                }
+            }
+
+            #[inline]
+            pub fn as_mut_slice(&mut self) -> &mut [$t] {
+                // This is safe because we are statically bounding our slices to the size of these
+                // vectors
+                unsafe {
+                    core::slice::from_raw_parts_mut(self as *mut $n as *mut $t, 3)
+                }
+            }
+
+            #[inline]
+            pub fn as_mut_byte_slice(&mut self) -> &mut [u8] {
+                // This is safe because we are statically bounding our slices to the size of these
+                // vectors
+                unsafe {
+                    core::slice::from_raw_parts_mut(self as *mut $n as *mut u8, 3 * core::mem::size_of::<$t>())
+                }
+            }
+
+            /// Returns a constant unsafe pointer to the underlying data in the underlying type.
+            /// This function is safe because all types here are repr(C) and can be represented
+            /// as their underlying type.
+            ///
+            /// # Safety
+            ///
+            /// It is up to the caller to correctly use this pointer and its bounds.
+            #[inline]
+            pub fn as_ptr(&self) -> *const $t {
+                self as *const $n as *const $t
+            }
+
+            /// Returns a mutable unsafe pointer to the underlying data in the underlying type.
+            /// This function is safe because all types here are repr(C) and can be represented
+            /// as their underlying type.
+            ///
+            /// # Safety
+            ///
+            /// It is up to the caller to correctly use this pointer and its bounds.
+            #[inline]
+            pub fn as_mut_ptr(&mut self) -> *mut $t {
+                self as *mut $n as *mut $t
+            }
+        }
+
+        impl From<[$t; 3]> for $n {
+            #[inline]
+            fn from(comps: [$t; 3]) -> Self {
+                Self::new(comps[0], comps[1], comps[2])
+            }
+        }
+        impl Into<[$t; 3]> for $n {
+            #[inline]
+            fn into(self) -> [$t; 3] {
+                [self.x, self.y, self.z]
+            }
+        }
+
+        // Hashing goes through the component array so that a vector and the array it borrows as
+        // hash identically, as required by `Borrow`.
+        impl Hash for $n {
+            #[inline]
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                let comps: &[$t; 3] = self.borrow();
+                comps.hash(state);
+            }
+        }
+
+        impl Borrow<[$t; 3]> for $n {
+            #[inline]
+            fn borrow(&self) -> &[$t; 3] {
+                // This is safe because the vector is repr(C) and consists of exactly 3
+                // components of the element type
+                unsafe { &*(self as *const $n as *const [$t; 3]) }
+            }
+        }
+
+        impl From<&[$t; 3]> for $n {
+            #[inline]
+            fn from(comps: &[$t; 3]) -> Self {
+               Self::from(*comps)
+            }
+        }
+
+        impl From<&mut [$t; 3]> for $n {
+            #[inline]
+            fn from(comps: &mut [$t; 3]) -> Self {
+                Self::from(*comps)
+            }
+        }
+
+       impl From<($t, $t, $t)> for $n {
+            #[inline]
+            fn from(comps: ($t, $t, $t)) -> Self {
+                Self::new(comps.0, comps.1, comps.2)
+            }
+        }
+
+        impl From<&($t, $t, $t)> for $n {
+            #[inline]
+            fn from(comps: &($t, $t, $t)) -> Self {
+                Self::from(*comps)
+            }
+        }
+
+        impl From<$n> for ($t, $t, $t) {
+            #[inline]
+            fn from(v: $n) -> Self {
+                (v.x, v.y, v.z)
+            }
+        }
+
+        impl From<($v2t, $t)> for $n {
+            #[inline]
+            fn from(comps: ($v2t, $t)) -> Self {
+                Self::new(comps.0.x, comps.0.y, comps.1)
+            }
+        }
+
+        impl From<(u8, u8, u8)> for $n {
+            #[inline]
+            fn from(comps: (u8, u8, u8)) -> Self {
+                $n { x: comps.0.into(), y: comps.1.into(), z: comps.2.into() }
+            }
+        }
+
+        impl From<(u16, u16, u16)> for $n {
+            #[inline]
+            fn from(comps: (u16, u16, u16)) -> Self {
+                $n { x: comps.0.into(), y: comps.1.into(), z: comps.2.into() }
+            }
+        }
+
+        impl TryFrom<(i64, i64, i64)> for $n {
+            type Error = TryFromIntError;
+
+            #[inline]
+            fn try_from(comps: (i64, i64, i64)) -> Result<Self, Self::Error> {
+                Ok($n { x: $t::try_from(comps.0)?, y: $t::try_from(comps.1)?, z: $t::try_from(comps.2)? })
+            }
+        }
+
+        impl TryFrom<(u64, u64, u64)> for $n {
+            type Error = TryFromIntError;
+
+            #[inline]
+            fn try_from(comps: (u64, u64, u64)) -> Result<Self, Self::Error> {
+                Ok($n { x: $t::try_from(comps.0)?, y: $t::try_from(comps.1)?, z: $t::try_from(comps.2)? })
+            }
+        }
+
+        impl Add for $n {
+            type Output = Self;
+            #[inline]
+            fn add(self, rhs: $n) -> Self {
+                $n::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+            }
+        }
+
+        impl AddAssign for $n {
+            #[inline]
+            fn add_assign(&mut self, rhs: $n) {
+                self.x += rhs.x;
+                self.y += rhs.y;
+                self.z += rhs.z;
+            }
+        }
+
+        impl Sub for $n {
+            type Output = Self;
+            #[inline]
+            fn sub(self, rhs: $n) -> Self {
+                $n::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+            }
+        }
+
+        impl SubAssign for $n {
+            #[inline]
+            fn sub_assign(&mut self, rhs: $n) {
+                self.x -= rhs.x;
+                self.y -= rhs.y;
+                self.z -= rhs.z;
+            }
+        }
+
+        impl Mul for $n {
+            type Output = Self;
+            #[inline]
+            fn mul(self, rhs: $n) -> Self {
+                $n::new(self.x * rhs.x, self.y * rhs.y, self.z * rhs.z)
+            }
+        }
+
+        impl Mul<$n> for $t {
+            type Output = $n;
+            #[inline]
+            fn mul(self, rhs: $n) -> $n {
+                $n::new(self * rhs.x, self * rhs.y, self * rhs.z)
+            }
+        }
+
+        impl Mul<$t> for $n {
+            type Output = $n;
+            #[inline]
+            fn mul(self, rhs: $t) -> $n {
+                $n::new(self.x * rhs, self.y * rhs, self.z * rhs)
+            }
+        }
+
+        impl MulAssign for $n {
+            #[inline]
+            fn mul_assign(&mut self, rhs: $n) {
+                self.x *= rhs.x;
+                self.y *= rhs.y;
+                self.z *= rhs.z;
+            }
+        }
+
+        impl MulAssign<$t> for $n {
+            #[inline]
+            fn mul_assign(&mut self, rhs: $t) {
+                self.x *= rhs;
+                self.y *= rhs;
+                self.z *= rhs;
+            }
+        }
+
+        impl Div for $n {
+            type Output = Self;
+            #[inline]
+            fn div(self, rhs: $n) -> Self {
+                $n::new(self.x / rhs.x, self.y / rhs.y, self.z / rhs.z)
+            }
+        }
+
+        impl Div<$t> for $n {
+            type Output = $n;
+            #[inline]
+            fn div(self, rhs: $t) -> $n {
+                $n::new(self.x / rhs, self.y / rhs, self.z / rhs)
+            }
+        }
+
+        impl DivAssign for $n {
+            #[inline]
+            fn div_assign(&mut self, rhs: $n) {
+                self.x /= rhs.x;
+                self.y /= rhs.y;
+                self.z /= rhs.z;
+            }
+        }
+
+        impl DivAssign<$t> for $n {
+            #[inline]
+            fn div_assign(&mut self, rhs: $t) {
+                self.x /= rhs;
+                self.y /= rhs;
+                self.z /= rhs;
+            }
+        }
+
+        impl MulAdd for $n {
+            type Output = Self;
+
+            #[inline]
+            fn mul_add(self, a: $n, b: $n) -> Self::Output {
+                $n::mul_add(&self, a, b)
+            }
+        }
+
+        impl IntVector for $n {
+            type Scalar = $t;
+            type Array = [$t; 3];
+            const DIM: usize = 3;
+
+            #[inline]
+            fn broadcast(val: $t) -> Self {
+                $n::broadcast(val)
+            }
+
+            #[inline]
+            fn dot(&self, other: Self) -> $t {
+                $n::dot(self, other)
+            }
+
+            #[inline]
+            fn component_sum(&self) -> $t {
+                $n::component_sum(self)
+            }
+
+            #[inline]
+            fn as_slice(&self) -> &[$t] {
+                $n::as_slice(self)
+            }
+
+            #[inline]
+            fn to_array(self) -> [$t; 3] {
+                self.into()
+            }
+        }
+
+        impl Index<usize> for $n {
+            type Output = $t;
+
+            fn index(&self, index: usize) -> &Self::Output {
+                match index {
+                    0 => &self.x,
+                    1 => &self.y,
+                    2 => &self.z,
+                    _ => panic!("Invalid for vector of type: {}", core::any::type_name::<$n>()),
+                }
+            }
+        }
+
+        impl IndexMut<usize> for $n {
+            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                match index {
+                    0 => &mut self.x,
+                    1 => &mut self.y,
+                    2 => &mut self.z,
+                    _ => panic!("Invalid for vector of type: {}", core::any::type_name::<$n>()),
+                }
+            }
+        }
+        )+
+    }
+}
+
+vec3i!((Vec2u, Vec3u, Vec4u) => u32);
+vec3i!((Vec2i, Vec3i, Vec4i) => i32);
+
+impl Vec3u {
+    /// Treating `self` as an extent, returns true if `point` lies in `0..self` on every axis.
+    #[inline]
+    pub fn contains_point(&self, point: Self) -> bool {
+        point.x < self.x && point.y < self.y && point.z < self.z
+    }
+
+    /// Treating `self` as an extent, returns true if `point` lies in `0..=self` on every axis.
+    #[inline]
+    pub fn contains_point_inclusive(&self, point: Self) -> bool {
+        point.x <= self.x && point.y <= self.y && point.z <= self.z
+    }
+
+    /// Apply a signed offset to this position, saturating each component at `0` and `u32::MAX`.
+    #[inline]
+    pub fn offset(&self, delta: Vec3i) -> Self {
+        Self::new(self.x.saturating_add_signed(delta.x), self.y.saturating_add_signed(delta.y), self.z.saturating_add_signed(delta.z))
+    }
+
+    /// Apply a signed offset to this position, returning `None` if any component would
+    /// underflow below zero or overflow past `u32::MAX`.
+    #[inline]
+    pub fn checked_add_signed(&self, delta: Vec3i) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add_signed(delta.x)?,
+            self.y.checked_add_signed(delta.y)?,
+            self.z.checked_add_signed(delta.z)?,
+        ))
+    }
+
+    /// The largest component, i.e. the infinity norm of this vector. This is the same as
+    /// `component_max` and exists to mirror the signed vectors.
+    #[inline]
+    pub fn abs_max(&self) -> u32 {
+        self.component_max()
+    }
+
+    /// The smallest component. This is the same as `component_min` and exists to mirror the
+    /// signed vectors.
+    #[inline]
+    pub fn abs_min(&self) -> u32 {
+        self.component_min()
+    }
+
+    /// The volume of the box with this vector as its extent, i.e. `x * y * z`.
+    ///
+    /// This overflows like any other `u32` multiplication, panicking in debug builds. Use
+    /// `volume_wide` if the extent may be large.
+    #[inline]
+    pub fn volume(&self) -> u32 {
+        self.x * self.y * self.z
+    }
+
+    /// Like `volume`, but computed in `u128`, which cannot overflow.
+    #[inline]
+    pub fn volume_wide(&self) -> u128 {
+        u128::from(self.x) * u128::from(self.y) * u128::from(self.z)
+    }
+
+    /// Rotate the bits of each component left by `n`, wrapping the truncated bits around to the
+    /// other end, unlike the shift operators.
+    #[inline]
+    pub fn rotate_left(self, n: u32) -> Self {
+        Self { x: self.x.rotate_left(n), y: self.y.rotate_left(n), z: self.z.rotate_left(n) }
+    }
+
+    /// Rotate the bits of each component right by `n`, wrapping the truncated bits around to the
+    /// other end, unlike the shift operators.
+    #[inline]
+    pub fn rotate_right(self, n: u32) -> Self {
+        Self { x: self.x.rotate_right(n), y: self.y.rotate_right(n), z: self.z.rotate_right(n) }
+    }
+
+    /// Rotate the bits of each component left by the matching component of `amounts`, which
+    /// like `u32::rotate_left` is taken modulo the bit width.
+    #[inline]
+    pub fn rotate_left_componentwise(self, amounts: Vec3u) -> Self {
+        Self { x: self.x.rotate_left(amounts.x), y: self.y.rotate_left(amounts.y), z: self.z.rotate_left(amounts.z) }
+    }
+
+    /// Rotate the bits of each component right by the matching component of `amounts`, which
+    /// like `u32::rotate_right` is taken modulo the bit width.
+    #[inline]
+    pub fn rotate_right_componentwise(self, amounts: Vec3u) -> Self {
+        Self { x: self.x.rotate_right(amounts.x), y: self.y.rotate_right(amounts.y), z: self.z.rotate_right(amounts.z) }
+    }
+
+    /// The wrapping difference `self - rhs`, along with a mask which is set for each component
+    /// that underflowed, i.e. where `self` is less than `rhs`.
+    #[inline]
+    pub fn overflowing_sub(&self, rhs: Self) -> (Self, BVec3) {
+        let (x, x_under) = self.x.overflowing_sub(rhs.x);
+        let (y, y_under) = self.y.overflowing_sub(rhs.y);
+        let (z, z_under) = self.z.overflowing_sub(rhs.z);
+        (Self { x, y, z }, BVec3::new(x_under, y_under, z_under))
+    }
+
+    /// The component-wise bitwise OR of every vector in `vecs`, starting from zero, so an
+    /// empty slice gives zero.
+    #[inline]
+    pub fn fold_or(vecs: &[Vec3u]) -> Vec3u {
+        let mut acc = Vec3u::zero();
+        for v in vecs {
+            acc.x |= v.x;
+            acc.y |= v.y;
+            acc.z |= v.z;
+        }
+        acc
+    }
+
+    /// The component-wise bitwise AND of every vector in `vecs`, starting from all ones, so an
+    /// empty slice gives `u32::MAX` in every component.
+    #[inline]
+    pub fn fold_and(vecs: &[Vec3u]) -> Vec3u {
+        let mut acc = Vec3u::broadcast(u32::MAX);
+        for v in vecs {
+            acc.x &= v.x;
+            acc.y &= v.y;
+            acc.z &= v.z;
+        }
+        acc
+    }
+
+    /// Map each component from `0..=u32::MAX` to `0.0..=1.0` by dividing by `u32::MAX`, as is
+    /// done to normalize integer colors. The division is done in `f64` before rounding to `f32`.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn to_normalized_f32(&self) -> crate::vec::Vec3 {
+        let norm = |c: u32| (f64::from(c) / f64::from(u32::MAX)) as f32;
+        crate::vec::Vec3::new(norm(self.x), norm(self.y), norm(self.z))
+    }
+}
+
+impl Vec3i {
+    #[inline]
+    pub fn flip_x(&self) -> Self {
+        Self { x: -self.x, ..*self }
+    }
+
+    #[inline]
+    pub fn flip_y(&self) -> Self {
+        Self { y: -self.y, ..*self }
+    }
+
+    #[inline]
+    pub fn flip_z(&self) -> Self {
+        Self { z: -self.z, ..*self }
+    }
+
+    /// Iterate `count` grid cells along a ray, yielding `start + step * i` for `i` in `0..count`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when reaching an index `i` greater than `i32::MAX`, rather than wrapping it
+    /// around to a negative step count.
+    #[inline]
+    pub fn iter_ray(start: Vec3i, step: Vec3i, count: usize) -> impl Iterator<Item = Vec3i> {
+        (0..count).map(move |i| start + step * i32::try_from(i).expect("iter_ray: step index exceeds i32::MAX"))
+    }
+
+    /// Returns true if `other` shares a face with this cell, i.e. the Manhattan distance
+    /// between them is exactly 1.
+    #[inline]
+    pub fn is_adjacent_4(&self, other: Self) -> bool {
+        let d = [(i64::from(self.x) - i64::from(other.x)).unsigned_abs(), (i64::from(self.y) - i64::from(other.y)).unsigned_abs(), (i64::from(self.z) - i64::from(other.z)).unsigned_abs()];
+        d.iter().sum::<u64>() == 1
+    }
+
+    /// Returns true if `other` touches this cell, orthogonally or diagonally, i.e. the
+    /// Chebyshev distance between them is exactly 1.
+    #[inline]
+    pub fn is_adjacent_8(&self, other: Self) -> bool {
+        let d = [(i64::from(self.x) - i64::from(other.x)).unsigned_abs(), (i64::from(self.y) - i64::from(other.y)).unsigned_abs(), (i64::from(self.z) - i64::from(other.z)).unsigned_abs()];
+        d.iter().max() == Some(&1)
+    }
+
+    /// Interpret this vector as cube hex coordinates and convert them to axial coordinates
+    /// `(q, r)` by dropping the redundant `z` component.
+    #[inline]
+    pub fn cube_to_axial(&self) -> Vec2i {
+        Vec2i::new(self.x, self.y)
+    }
+
+    /// The number of hex steps between two cells given in cube coordinates.
+    ///
+    /// The differences are taken in `i64`, so distant cells cannot overflow along the way.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the distance does not fit in an `i32`.
+    #[inline]
+    pub fn hex_distance(&self, other: Vec3i) -> i32 {
+        let d = self.zip(other).map(|(a, b)| (i64::from(a) - i64::from(b)).unsigned_abs()).max().unwrap_or(0);
+        i32::try_from(d).expect("Hex distance overflows i32")
+    }
+
+    /// The face normal of the triangle `a`, `b`, `c`, i.e. `(b - a).cross(c - a)`.
+    ///
+    /// The normal is not normalized, and its length is twice the area of the triangle, so it
+    /// can be much larger than the input coordinates. The edges and cross product are computed
+    /// in `i128`, so intermediate values never overflow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a component of the normal does not fit in an `i32`.
+    #[inline]
+    pub fn face_normal(a: Vec3i, b: Vec3i, c: Vec3i) -> Vec3i {
+        let edge = |p: Vec3i| {
+            [
+                i128::from(p.x) - i128::from(a.x),
+                i128::from(p.y) - i128::from(a.y),
+                i128::from(p.z) - i128::from(a.z),
+            ]
+        };
+        let (ab, ac) = (edge(b), edge(c));
+        let narrow = |c: i128| i32::try_from(c).expect("Face normal component overflows i32");
+        Vec3i::new(
+            narrow(ab[1] * ac[2] - ab[2] * ac[1]),
+            narrow(ab[2] * ac[0] - ab[0] * ac[2]),
+            narrow(ab[0] * ac[1] - ab[1] * ac[0]),
+        )
+    }
+
+    /// The volume of the box with this vector as its extent, i.e. `x * y * z`.
+    ///
+    /// This overflows like any other `i32` multiplication, panicking in debug builds. Use
+    /// `volume_wide` if the extent may be large.
+    #[inline]
+    pub fn volume(&self) -> i32 {
+        self.x * self.y * self.z
+    }
+
+    /// Like `volume`, but computed in `i128`, which cannot overflow.
+    #[inline]
+    pub fn volume_wide(&self) -> i128 {
+        i128::from(self.x) * i128::from(self.y) * i128::from(self.z)
+    }
+
+    /// The centroid (mean) of `points`, rounded to the nearest integer with ties rounded away
+    /// from zero. The sum is accumulated in `i64`, so it cannot overflow for any realistic
+    /// number of points.
+    ///
+    /// Returns `Vec3i::zero()` for an empty slice.
+    #[inline]
+    pub fn centroid(points: &[Vec3i]) -> Vec3i {
+        if points.is_empty() {
+            return Vec3i::zero();
+        }
+        let mut sum = [0i64; 3];
+        for p in points {
+            sum[0] += i64::from(p.x);
+            sum[1] += i64::from(p.y);
+            sum[2] += i64::from(p.z);
+        }
+        let count = points.len() as i64;
+        Vec3i { x: div_round(sum[0], count) as i32, y: div_round(sum[1], count) as i32, z: div_round(sum[2], count) as i32 }
+    }
+
+    /// The octant (`0..8`) of `center` that this point falls into, as used when descending
+    /// an octree. Bit 0 is set if `x >= center.x`, bit 1 if `y >= center.y` and bit 2 if
+    /// `z >= center.z`, so points lying exactly on one of the center's planes are counted on
+    /// the positive side.
+    #[inline]
+    pub fn octant(&self, center: Vec3i) -> u8 {
+        (self.x >= center.x) as u8 | ((self.y >= center.y) as u8) << 1 | ((self.z >= center.z) as u8) << 2
+    }
+
+    /// Split this coordinate into its position within a layer, `xy`, and the index of that
+    /// layer, `z`.
+    #[inline]
+    pub fn to_layered(&self) -> (Vec2i, i32) {
+        (Vec2i::new(self.x, self.y), self.z)
+    }
+
+    /// Recombine a position within a layer and a layer index into a 3d coordinate. This is
+    /// the inverse of `to_layered`.
+    #[inline]
+    pub fn from_layered(xy: Vec2i, layer: i32) -> Vec3i {
+        Vec3i::new(xy.x, xy.y, layer)
+    }
+
+    /// Iterate the 6 cells sharing a face with this one.
+    #[inline]
+    pub fn neighbors_6(&self) -> impl Iterator<Item = Vec3i> {
+        self.neighbors_within(1)
+    }
+
+    /// Iterate the 18 cells sharing a face or an edge with this one.
+    #[inline]
+    pub fn neighbors_18(&self) -> impl Iterator<Item = Vec3i> {
+        self.neighbors_within(2)
+    }
+
+    /// Iterate the 26 cells sharing a face, an edge or a corner with this one.
+    #[inline]
+    pub fn neighbors_26(&self) -> impl Iterator<Item = Vec3i> {
+        self.neighbors_within(3)
+    }
+
+    /// Iterate the cells offset from this one by `-1`, `0` or `1` along each axis, with
+    /// between one and `max_axes` of the offsets non-zero. Cells are yielded in `x`, `y`, `z`
+    /// order from the most negative offset.
+    #[inline]
+    fn neighbors_within(&self, max_axes: usize) -> impl Iterator<Item = Vec3i> {
+        let cell = *self;
+        (0..27)
+            .map(|i| Vec3i::new(i % 3 - 1, i / 3 % 3 - 1, i / 9 - 1))
+            .filter(move |offset| (1..=max_axes).contains(&offset.count_matching(|c| c != 0)))
+            .map(move |offset| cell + offset)
+    }
+
+    /// Clamp each component into the inclusive bounds of `aabb`, giving the closest point
+    /// inside it.
+    #[inline]
+    pub fn clamp_to_aabb(&self, aabb: &IntAabb3) -> Self {
+        self.clamped(aabb.min, aabb.max)
+    }
+}
+
+macro_rules! vec4i {
+    ($($n:ident, $v2t:ident, $v3t:ident => $t:ident),+) => {
+        /// A set of four coordinates which may be interpreted as a point or vector in 4d space,
+        /// or as a homogeneous 3d vector or point.
+        ///
+        /// Generally this distinction between a point and vector is more of a pain than it is worth
+        /// to distinguish on a type level, however when converting to and from homogeneous
+        /// coordinates it is quite important.
+        $(#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        #[repr(C)]
+        pub struct $n {
+            pub x: $t,
+            pub y: $t,
+            pub z: $t,
+            pub w: $t,
+        }
+
+        impl $n {
+            #[inline]
+            pub fn new<T: Into<$t>>(x: T, y: T, z: T, w: T) -> Self {
+                $n { x: x.into(), y: y.into(), z: z.into(), w: w.into() }
+            }
+
+            #[inline]
+            pub fn broadcast<T: Into<$t> + Copy>(val: T) -> Self {
+                Self::new(val, val, val, val)
+            }
+
+            #[inline]
+            pub fn unit_x() -> Self {
+                $n{ x: 1, y: 0, z: 0, w: 0 }
+            }
+
+            #[inline]
+            pub fn unit_y() -> Self {
+                $n{ x: 0, y: 1, z: 0, w: 0 }
+            }
+
+            #[inline]
+            pub fn unit_z() -> Self {
+                $n{ x: 0, y: 0, z: 1, w: 0 }
+            }
+
+            #[inline]
+            pub fn unit_w() -> Self {
+                $n{ x: 0, y: 0, z: 0, w: 1 }
+            }
+
+            #[inline]
+            pub fn dot(&self, other: $n) -> $t {
+                self.x.mul_add(other.x, self.y.mul_add(other.y, self.z.mul_add(other.z, self.w * other.w)))
+            }
+
+            #[inline]
+            pub fn reflect(&mut self, normal: $n) {
+                *self -= 2 * self.dot(normal) * normal;
+            }
+
+            #[inline]
+            pub fn reflected(&self, normal: $n) -> Self {
+                let mut a = *self;
+                a.reflect(normal);
+                a
+            }
+
+            #[inline]
+            pub fn mag(&self) -> $t {
+                isqrt_u128(self.mag_sq().max(0) as u128) as $t
+            }
+
+            #[inline]
+            pub fn mag_sq(&self) -> $t {
+                self.x.mul_add(self.x, self.y.mul_add(self.y, self.z.mul_add(self.z, self.w * self.w)))
+            }
+
+            #[inline]
+            pub fn mul_add(&self, mul: $n, add: $n) -> Self {
+                $n::new(
+                    self.x.mul_add(mul.x, add.x),
+                    self.y.mul_add(mul.y, add.y),
+                    self.z.mul_add(mul.z, add.z),
+                    self.w.mul_add(mul.w, add.w),
+                )
+            }
+
+            #[inline]
+            pub fn clamp(&mut self, min: Self, max: Self) {
+                self.x = self.x.max(min.x).min(max.x);
+                self.y = self.y.max(min.y).min(max.y);
+                self.z = self.z.max(min.z).min(max.z);
+                self.w = self.w.max(min.w).min(max.w);
+            }
+
+            #[inline]
+            pub fn clamped(mut self, min: Self, max: Self) -> Self {
+                self.clamp(min, max);
+                self
+            }
+
+            #[inline]
+            pub fn map<F>(&self, f: F) -> Self
+                where F: Fn($t) -> $t
+            {
+                $n::new(
+                    f(self.x),
+                    f(self.y),
+                    f(self.z),
+                    f(self.w),
+                )
+            }
+
+            #[inline]
+            pub fn apply<F>(&mut self, f: F)
+                where F: Fn($t) -> $t
+            {
+                self.x = f(self.x);
+                self.y = f(self.y);
+                self.z = f(self.z);
+                self.w = f(self.w);
+            }
+
+            #[inline]
+            pub fn max_by_component(mut self, other: Self) -> Self {
+                self.x = self.x.max(other.x);
+                self.y = self.y.max(other.y);
+                self.z = self.z.max(other.z);
+                self.w = self.w.max(other.w);
+                self
+            }
+
+            #[inline]
+            pub fn min_by_component(mut self, other: Self) -> Self {
+                self.x = self.x.min(other.x);
+                self.y = self.y.min(other.y);
+                self.z = self.z.min(other.z);
+                self.w = self.w.min(other.w);
+                self
+            }
+
+            #[inline]
+            pub fn component_max(&self) -> $t {
+                self.x.max(self.y).max(self.z).max(self.w)
+            }
+
+            #[inline]
+            pub fn component_min(&self) -> $t {
+                self.x.min(self.y).min(self.z).min(self.w)
+            }
+
+            /// Like `min_by_component`, but also returns a mask which is set for each component
+            /// taken from `other`. On ties the component from `self` is kept.
+            #[inline]
+            pub fn min_by_component_tracked(&self, other: Self) -> (Self, BVec4) {
+                let mask = BVec4::new(other.x < self.x, other.y < self.y, other.z < self.z, other.w < self.w);
+                (self.min_by_component(other), mask)
+            }
+
+            /// Like `max_by_component`, but also returns a mask which is set for each component
+            /// taken from `other`. On ties the component from `self` is kept.
+            #[inline]
+            pub fn max_by_component_tracked(&self, other: Self) -> (Self, BVec4) {
+                let mask = BVec4::new(other.x > self.x, other.y > self.y, other.z > self.z, other.w > self.w);
+                (self.max_by_component(other), mask)
+            }
+
+            /// A fast hash of the coordinates, intended for custom spatial hash maps where the
+            /// default `Hash` implementation and hasher are too slow.
+            ///
+            /// Each component's 32 bits are folded in using the FxHash step (rotate left by 5, xor,
+            /// multiply by `0x517cc1b727220a95`) and the result is finished with the MurmurHash3
+            /// `fmix64` finalizer. The hash is deterministic across platforms and runs.
+            #[inline]
+            pub fn spatial_hash(&self) -> u64 {
+                let mut hash = 0u64;
+                for &c in self.as_slice() {
+                    hash = (hash.rotate_left(5) ^ (c as u64 & 0xffff_ffff)).wrapping_mul(SPATIAL_HASH_SEED);
+                }
+                fmix64(hash)
+            }
+
+            /// Promote each component to `f64`, which represents every 32-bit integer exactly.
+            #[inline]
+            pub fn to_f64_array(&self) -> [f64; 4] {
+                [self.x as f64, self.y as f64, self.z as f64, self.w as f64]
+            }
+
+            /// Convert each component to `f32`, which rounds integers with a magnitude above `2^24`.
+            #[inline]
+            pub fn to_f32_array(&self) -> [f32; 4] {
+                [self.x as f32, self.y as f32, self.z as f32, self.w as f32]
+            }
+
+            /// Round each component down to a multiple of the corresponding component of `multiple`,
+            /// using Euclidean division so that negative values round towards negative infinity.
+            /// Components with a multiple of zero are left unchanged.
+            #[inline]
+            pub fn align_down(&self, multiple: Self) -> Self {
+                let align = |c: $t, m: $t| if m == 0 { c } else { c - c.rem_euclid(m) };
+                $n::new(
+                    align(self.x, multiple.x),
+                    align(self.y, multiple.y),
+                    align(self.z, multiple.z),
+                    align(self.w, multiple.w),
+                )
+            }
+
+            /// Round each component up to a multiple of the corresponding component of `multiple`,
+            /// using Euclidean division so that negative values round towards positive infinity.
+            /// Components with a multiple of zero are left unchanged.
+            #[inline]
+            pub fn align_up(&self, multiple: Self) -> Self {
+                let align = |c: $t, m: $t| {
+                    let r = if m == 0 { 0 } else { c.rem_euclid(m) };
+                    if r == 0 {
+                        c
+                    } else if m > 0 {
+                        c + (m - r)
+                    } else {
+                        c - r - m
+                    }
+                };
+                $n::new(
+                    align(self.x, multiple.x),
+                    align(self.y, multiple.y),
+                    align(self.z, multiple.z),
+                    align(self.w, multiple.w),
+                )
+            }
+
+            /// The components of this vector as little-endian bytes, in `x, y, ...` order.
+            #[inline]
+            pub fn to_le_bytes(&self) -> [u8; 4 * core::mem::size_of::<$t>()] {
+                let mut bytes = [0; 4 * core::mem::size_of::<$t>()];
+                for (chunk, c) in bytes.chunks_exact_mut(core::mem::size_of::<$t>()).zip(self.as_slice()) {
+                    chunk.copy_from_slice(&c.to_le_bytes());
+                }
+                bytes
+            }
+
+            /// Create a vector from components stored as little-endian bytes, in `x, y, ...` order.
+            #[inline]
+            pub fn from_le_bytes(bytes: [u8; 4 * core::mem::size_of::<$t>()]) -> Self {
+                let mut v = Self::zero();
+                for (c, chunk) in v.as_mut_slice().iter_mut().zip(bytes.chunks_exact(core::mem::size_of::<$t>())) {
+                    let mut comp = [0; core::mem::size_of::<$t>()];
+                    comp.copy_from_slice(chunk);
+                    *c = $t::from_le_bytes(comp);
+                }
+                v
+            }
+
+            /// The components of this vector as big-endian bytes, in `x, y, ...` order.
+            #[inline]
+            pub fn to_be_bytes(&self) -> [u8; 4 * core::mem::size_of::<$t>()] {
+                let mut bytes = [0; 4 * core::mem::size_of::<$t>()];
+                for (chunk, c) in bytes.chunks_exact_mut(core::mem::size_of::<$t>()).zip(self.as_slice()) {
+                    chunk.copy_from_slice(&c.to_be_bytes());
+                }
+                bytes
+            }
+
+            /// Create a vector from components stored as big-endian bytes, in `x, y, ...` order.
+            #[inline]
+            pub fn from_be_bytes(bytes: [u8; 4 * core::mem::size_of::<$t>()]) -> Self {
+                let mut v = Self::zero();
+                for (c, chunk) in v.as_mut_slice().iter_mut().zip(bytes.chunks_exact(core::mem::size_of::<$t>())) {
+                    let mut comp = [0; core::mem::size_of::<$t>()];
+                    comp.copy_from_slice(chunk);
+                    *c = $t::from_be_bytes(comp);
+                }
+                v
+            }
+
+            /// Cyclically shift the components one place towards `x`, so `(x, y, z, w)` becomes `(y, z, w, x)`.
+            #[inline]
+            pub fn rotate_components(&self) -> Self {
+                $n::new(self.y, self.z, self.w, self.x)
+            }
+
+            /// Cyclically shift the components one place away from `x`, so `(x, y, z, w)` becomes `(w, x, y, z)`.
+            #[inline]
+            pub fn rotate_components_rev(&self) -> Self {
+                $n::new(self.w, self.x, self.y, self.z)
+            }
+
+            /// Cyclically shift the components `n` places towards `x`, so `rotate_components_by(1)`
+            /// is the same as `rotate_components`. `n` is taken modulo the dimension.
+            #[inline]
+            pub fn rotate_components_by(&self, n: usize) -> Self {
+                let mut comps: [$t; 4] = (*self).into();
+                comps.rotate_left(n % 4);
+                comps.into()
+            }
+
+            /// The component along the given axis (0 for `x`, 1 for `y`, ...), or `None` if the axis
+            /// is out of range for this vector.
+            #[inline]
+            pub fn component(&self, axis: usize) -> Option<$t> {
+                self.as_slice().get(axis).copied()
+            }
+
+            /// Returns a copy of this vector with the component along the given axis replaced by `value`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the axis is out of range for this vector.
+            #[inline]
+            pub fn with_component(mut self, axis: usize, value: $t) -> Self {
+                match self.as_mut_slice().get_mut(axis) {
+                    Some(c) => *c = value,
+                    None => panic!("Invalid axis {} for vector of type: {}", axis, core::any::type_name::<$n>()),
+                }
+                self
+            }
+
+            /// Split this vector into its low and high halves, `(xy, zw)`.
+            #[inline]
+            pub fn split_halves(&self) -> ($v2t, $v2t) {
+                (self.xy(), self.zw())
+            }
+
+            /// Create a vector from its low and high halves, the inverse of `split_halves`.
+            #[inline]
+            pub fn from_halves(lo: $v2t, hi: $v2t) -> Self {
+                $n::new(lo.x, lo.y, hi.x, hi.y)
+            }
+
+            /// The smallest and largest components, `(component_min, component_max)`, computed in a
+            /// single pass.
+            #[inline]
+            pub fn component_min_max(&self) -> ($t, $t) {
+                let (mut min, mut max) = (self.x, self.x);
+                for &c in &[self.y, self.z, self.w] {
+                    if c < min {
+                        min = c;
+                    } else if c > max {
+                        max = c;
+                    }
+                }
+                (min, max)
+            }
+
+            /// Like `mul_add`, but returns `None` if any component of the result does not fit in the
+            /// element type. The multiplication and addition are computed in `i128`, so an
+            /// intermediate product which overflows is fine as long as the final sum fits.
+            #[inline]
+            pub fn checked_mul_add(&self, mul: Self, add: Self) -> Option<Self> {
+                let fma = |c: $t, m: $t, a: $t| $t::try_from(c as i128 * m as i128 + a as i128).ok();
+                Some($n { x: fma(self.x, mul.x, add.x)?, y: fma(self.y, mul.y, add.y)?, z: fma(self.z, mul.z, add.z)?, w: fma(self.w, mul.w, add.w)? })
+            }
+
+            /// Returns an iterator over mutable references to the components of this vector, in order.
+            #[inline]
+            pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, $t> {
+                self.as_mut_slice().iter_mut()
+            }
+
+            /// Returns the per-component median of `a`, `b` and `c`.
+            ///
+            /// Clamping `v` between `lo` and `hi` with `lo <= hi` is equivalent to `median(v, lo, hi)`.
+            #[inline]
+            pub fn median(a: Self, b: Self, c: Self) -> Self {
+                a.min_by_component(b)
+                    .max_by_component(a.max_by_component(b).min_by_component(c))
+            }
+
+            /// Convert this vector to another vector type, failing if any component is out of range
+            /// for the target, e.g. `Vec3i::new(1, 2, 3).try_cast::<Vec3u>()`.
+            #[inline]
+            pub fn try_cast<T: TryFrom<Self>>(&self) -> Result<T, T::Error> {
+                T::try_from(*self)
+            }
+
+            /// Compare two vectors under the component-wise partial order.
+            ///
+            /// Returns `Less` if every component of `self` is `<=` the matching component of `other`
+            /// and at least one is strictly less, `Greater` in the symmetric case, `Equal` if all
+            /// components are equal and `None` if the vectors are incomparable.
+            #[inline]
+            pub fn partial_cmp_componentwise(&self, other: Self) -> Option<core::cmp::Ordering> {
+                let mut less = false;
+                let mut greater = false;
+                for (a, b) in self.as_slice().iter().zip(other.as_slice()) {
+                    less |= a < b;
+                    greater |= a > b;
+                }
+                match (less, greater) {
+                    (false, false) => Some(core::cmp::Ordering::Equal),
+                    (true, false) => Some(core::cmp::Ordering::Less),
+                    (false, true) => Some(core::cmp::Ordering::Greater),
+                    (true, true) => None,
+                }
+            }
+
+            /// Compute the weighted sum `Σ wᵢ·vᵢ` of the given `(weight, vector)` terms.
+            ///
+            /// The sum is accumulated in `i128`, so intermediate products and partial sums never
+            /// overflow; only the final result must fit in the element type.
+            ///
+            /// # Panics
+            ///
+            /// Panics if a component of the result does not fit in the element type.
+            #[inline]
+            pub fn weighted_sum(terms: &[($t, Self)]) -> Self {
+                let mut acc = [0i128; 4];
+                for &(weight, v) in terms {
+                    for (a, &c) in acc.iter_mut().zip(v.as_slice()) {
+                        *a += weight as i128 * c as i128;
+                    }
+                }
+                let mut sum = Self::zero();
+                for (c, a) in sum.iter_mut().zip(acc.iter()) {
+                    *c = $t::try_from(*a).expect("Weighted sum component overflows the element type");
+                }
+                sum
+            }
+
+            /// Count the components for which `pred` returns true.
+            #[inline]
+            pub fn count_matching<F>(&self, pred: F) -> usize
+                where F: Fn($t) -> bool
+            {
+                self.as_slice().iter().filter(|&&c| pred(c)).count()
+            }
+
+            /// The squared Euclidean distance between this point and `other`, computed in `u128`
+            /// so that it cannot overflow.
+            #[inline]
+            pub fn distance_sq_to(&self, other: Self) -> u128 {
+                self.as_slice()
+                    .iter()
+                    .zip(other.as_slice())
+                    .map(|(&a, &b)| {
+                        let d = (a as i64 - b as i64).unsigned_abs() as u128;
+                        d * d
+                    })
+                    .sum()
+            }
+
+            /// Returns a comparator ordering points by their squared distance to `reference`, for use
+            /// with `sort_by` and friends.
+            #[inline]
+            pub fn cmp_by_distance_sq(reference: Self) -> impl Fn(&Self, &Self) -> core::cmp::Ordering {
+                move |a, b| a.distance_sq_to(reference).cmp(&b.distance_sq_to(reference))
+            }
+
+            /// Returns true if every component lies in the half-open range `min..max`, i.e. is at
+            /// least the matching component of `min` and less than that of `max`.
+            #[inline]
+            pub fn in_bounds(&self, min: Self, max: Self) -> bool {
+                self.as_slice()
+                    .iter()
+                    .zip(min.as_slice().iter().zip(max.as_slice()))
+                    .all(|(c, (lo, hi))| lo <= c && c < hi)
+            }
+
+            /// Like `mul_add`, but with the same scalar multiplier for every component, i.e.
+            /// `self * mul + add`.
+            #[inline]
+            pub fn mul_add_scalar(&self, mul: $t, add: $n) -> Self {
+                self.mul_add($n::broadcast(mul), add)
+            }
+
+            /// The component at index `i` taken modulo the dimension, so that the components are
+            /// treated as a cyclic buffer and e.g. `-1` returns the last component.
+            #[inline]
+            pub fn wrapping_component(&self, i: isize) -> $t {
+                self.as_slice()[i.rem_euclid(4) as usize]
+            }
+
+            /// Returns an iterator over the components of this vector, in order. The iterator is
+            /// double-ended, so `v.iter().rev()` walks the components from last to first.
+            #[inline]
+            pub fn iter(&self) -> core::slice::Iter<'_, $t> {
+                self.as_slice().iter()
+            }
+
+            /// Returns an iterator over the components of this vector from last to first.
+            #[inline]
+            pub fn iter_rev(&self) -> core::iter::Rev<core::slice::Iter<'_, $t>> {
+                self.iter().rev()
+            }
+
+            /// Treating this vector as a homogeneous 3d point, divide the other components by the
+            /// homogeneous component `w`, giving the equivalent point with `w == 1`.
+            ///
+            /// Returns `None` if `w` is zero (the vector is a homogeneous 3d *vector*, not a point)
+            /// or does not divide every other component exactly, in which case the point has no
+            /// integer representation with `w == 1`. The division overflowing also returns `None`.
+            #[inline]
+            pub fn homogenize(&self) -> Option<Self> {
+                let div = |c: $t| match c.checked_rem(self.w) {
+                    Some(0) => c.checked_div(self.w),
+                    _ => None,
+                };
+                Some(Self { x: div(self.x)?, y: div(self.y)?, z: div(self.z)?, w: 1 })
+            }
+
+            /// The sum of all components.
+            #[inline]
+            pub fn component_sum(&self) -> $t {
+                self.x + self.y + self.z + self.w
+            }
+
+            /// Returns an iterator over pairs of matching components of `self` and `other`, in order.
+            #[inline]
+            pub fn zip(&self, other: Self) -> impl Iterator<Item = ($t, $t)> {
+                let this = *self;
+                (0..4).map(move |i| (this[i], other[i]))
+            }
+
+            /// Interpolate `percent` percent of the way from `self` to `end`, rounding each component
+            /// to the nearest integer with ties rounded away from `self`. `percent` is clamped to
+            /// `0..=100`, and the math is done in `i64` so it cannot overflow.
+            #[inline]
+            pub fn lerp_percent(&self, end: Self, percent: $t) -> Self {
+                let percent = i64::from(percent.clamp(0, 100));
+                let mut out = *self;
+                for (c, &e) in out.iter_mut().zip(end.as_slice()) {
+                    let start = i64::from(*c);
+                    *c = (start + div_round((i64::from(e) - start) * percent, 100)) as $t;
+                }
+                out
+            }
+
+            /// Combine each pair of matching components of `self` and `other` with `f`, collecting
+            /// the results in an array. Unlike `map`, the output may be of any type.
+            #[inline]
+            pub fn map2_to<U, F>(&self, other: Self, f: F) -> [U; 4]
+                where F: Fn($t, $t) -> U
+            {
+                [f(self.x, other.x), f(self.y, other.y), f(self.z, other.z), f(self.w, other.w)]
+            }
+
+            /// The Manhattan distance between `self` and `other` on a grid of size `extent` which
+            /// wraps around at its edges, like the surface of a torus. Along each axis the shorter of
+            /// the direct and the wrapped-around difference is taken, and these are summed.
+            ///
+            /// Both points should lie within `0..extent`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if any component of `extent` is not positive, or if the distance does not fit
+            /// in the element type.
+            #[inline]
+            pub fn toroidal_distance(&self, other: Self, extent: Self) -> $t {
+                let mut sum = 0i64;
+                for (i, &e) in extent.as_slice().iter().enumerate() {
+                    assert!(e > 0, "Toroidal extent must be positive on every axis");
+                    let e = i64::from(e);
+                    let d = (i64::from(self[i]) - i64::from(other[i])).rem_euclid(e);
+                    sum += d.min(e - d);
+                }
+                $t::try_from(sum).expect("Toroidal distance overflows the element type")
+            }
+
+            /// Like `component_sum`, but returns `None` if the sum overflows.
+            #[inline]
+            pub fn checked_component_sum(&self) -> Option<$t> {
+                self.iter().try_fold(0 as $t, |acc, &c| acc.checked_add(c))
+            }
+
+            /// The product of all components, or `None` if it overflows. This is useful for sizing a
+            /// buffer from an extent before allocating it.
+            #[inline]
+            pub fn checked_component_product(&self) -> Option<$t> {
+                self.iter().try_fold(1 as $t, |acc, &c| acc.checked_mul(c))
+            }
+
+            /// Convert this vector into an array whose length is a const generic parameter, for use
+            /// with code which is generic over the array length. Using a length other than the
+            /// vector's dimension is a compile-time error.
+            #[inline]
+            pub fn into_const_array<const N: usize>(self) -> [$t; N] {
+                let () = AssertLen::<N, 4>::OK;
+                let mut comps = [0 as $t; N];
+                comps.copy_from_slice(self.as_slice());
+                comps
+            }
+
+            /// Raise each component to the power `exp`, saturating at the bounds of the element
+            /// type instead of overflowing.
+            #[inline]
+            pub fn saturating_pow(self, exp: u32) -> Self {
+                $n { x: self.x.saturating_pow(exp), y: self.y.saturating_pow(exp), z: self.z.saturating_pow(exp), w: self.w.saturating_pow(exp) }
+            }
+
+            /// The component-wise greatest common divisor of `self` and `other`, which is always
+            /// non-negative. `gcd(0, n)` is `|n|`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the result does not fit in the element type, which is only possible when
+            /// both components are `0` or `i32::MIN` and at least one is `i32::MIN`.
+            #[inline]
+            pub fn gcd(&self, other: Self) -> Self {
+                self.zip_with_u64(other, gcd_u64, "GCD overflows the element type")
+            }
+
+            /// The component-wise least common multiple of `self` and `other`, which is always
+            /// non-negative. `lcm(0, n)` is `0`.
+            ///
+            /// # Panics
+            ///
+            /// The LCM is computed in `u64` and so never overflows internally, but this panics if
+            /// the result does not fit in the element type.
+            #[inline]
+            pub fn lcm(&self, other: Self) -> Self {
+                self.zip_with_u64(other, lcm_u64, "LCM overflows the element type")
+            }
+
+            /// Combine the absolute values of matching components with `f`, converting each result
+            /// back to the element type.
+            #[inline]
+            fn zip_with_u64(&self, other: Self, f: fn(u64, u64) -> u64, overflow: &str) -> Self {
+                let mut out = *self;
+                for (c, &o) in out.iter_mut().zip(other.as_slice()) {
+                    let r = f((*c as i64).unsigned_abs(), (o as i64).unsigned_abs());
+                    *c = $t::try_from(r).expect(overflow);
+                }
+                out
+            }
+
+            /// The unit vector along the given axis (0 for `x`, 1 for `y`, ...), for when the axis
+            /// is only known at runtime.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the axis is out of range for this vector, like `with_component`.
+            #[inline]
+            pub fn unit_axis(axis: usize) -> Self {
+                Self::zero().with_component(axis, 1)
+            }
+
+            /// Perform the perspective divide on this homogeneous 3d point, dividing `x`, `y` and `z`
+            /// by `w` with truncation towards zero. This is the same as `from_homogeneous_point` on
+            /// the 3d vector type, but returns `None` instead of panicking when `w` is zero, since the
+            /// vector is then a direction rather than a point, or when the division overflows.
+            #[inline]
+            pub fn perspective_divide(&self) -> Option<$v3t> {
+                $v3t::checked_from_homogeneous_point(*self)
+            }
+
+            /// The distance between `self` and `other` under the metric `M`. The per-axis
+            /// differences and the metric are computed in wider types, so only the final distance
+            /// needs to fit in the element type.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the distance does not fit in the element type.
+            #[inline]
+            pub fn distance<M: Metric>(&self, other: Self) -> $t {
+                let mut diffs = [0u64; 4];
+                for (d, (&a, &b)) in diffs.iter_mut().zip(self.as_slice().iter().zip(other.as_slice())) {
+                    *d = (a as i64 - b as i64).unsigned_abs();
+                }
+                $t::try_from(M::combine(&diffs)).expect("Distance overflows the element type")
+            }
+
+            /// Like `apply`, but also passes the index of each component (0 for `x`, 1 for `y`, ...)
+            /// to `f`.
+            #[inline]
+            pub fn apply_indexed<F>(&mut self, f: F)
+                where F: Fn(usize, $t) -> $t
+            {
+                for (i, c) in self.iter_mut().enumerate() {
+                    *c = f(i, *c);
+                }
+            }
+
+            /// Build a vector whose `i`th component is the component of `self` at `indices[i]`,
+            /// a swizzle chosen at runtime. Indices may repeat.
+            ///
+            /// # Panics
+            ///
+            /// Panics if any index is out of range for this vector, like indexing does.
+            #[inline]
+            pub fn gather(&self, indices: [usize; 4]) -> Self {
+                $n { x: self[indices[0]], y: self[indices[1]], z: self[indices[2]], w: self[indices[3]] }
+            }
+
+            /// Clamp each component between `min` and `max` like `Ord::clamp`.
+            ///
+            /// Unlike the lenient [`clamped`](Self::clamped), which silently prefers `max` when the
+            /// bounds are inverted, this enforces the same contract as `std`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `min` is greater than `max` on any axis.
+            #[inline]
+            pub fn clamp_ord(self, min: Self, max: Self) -> Self {
+                assert!(!(min.x > max.x || min.y > max.y || min.z > max.z || min.w > max.w), "clamp_ord: min must not exceed max on any axis");
+                self.clamped(min, max)
+            }
+
+            /// Scale each component by the fraction `num / den`, rounding to the nearest integer with
+            /// ties rounded away from zero. The product is computed in `i128` so it cannot overflow.
+            ///
+            /// Returns `None` if `den` is zero or a scaled component does not fit in the component type.
+            #[inline]
+            pub fn scale_rational(&self, num: $t, den: $t) -> Option<Self> {
+                if den == 0 {
+                    return None;
+                }
+                let mut out = *self;
+                for c in out.iter_mut() {
+                    let scaled = div_round_i128(i128::from(*c) * i128::from(num), i128::from(den));
+                    *c = <$t>::try_from(scaled).ok()?;
+                }
+                Some(out)
+            }
+
+            #[inline]
+            pub fn zero() -> Self {
+                Self::broadcast(0 as $t)
+            }
+
+            #[inline]
+            pub fn one() -> Self {
+                Self::broadcast(1 as $t)
+            }
+
+            #[inline]
+            pub fn xy(&self) -> $v2t {
+                $v2t::new(self.x, self.y)
+            }
+
+             #[inline]
+            pub fn xyz(&self) -> $v3t {
+                $v3t::new(self.x, self.y, self.z)
+            }
+
+            #[inline]
+            pub fn zw(&self) -> $v2t {
+                $v2t::new(self.z, self.w)
+            }
+
+
+            #[cfg(feature = "alloc")]
+            #[inline]
+            pub fn layout() -> alloc::alloc::Layout {
+                alloc::alloc::Layout::from_size_align(core::mem::size_of::<Self>(), core::mem::align_of::<$t>()).unwrap()
+            }
+
+            #[inline]
+            pub fn as_slice(&self) -> &[$t] {
+                // This is safe because we are statically bounding our slices to the size of these
+                // vectors
+                unsafe {
+                    core::slice::from_raw_parts(self as *const $n as *const $t, 4)
+                }
+            }
+
+            #[inline]
+            pub fn as_byte_slice(&self) -> &[u8] {
+                // This is safe because we are statically bounding our slices to the size of these
+                // vectors
+                unsafe {
+                    core::slice::from_raw_parts(self as *const $n as *const u8, 4 * core::mem::size_of::<$t>())
+                }
+            }
+
+            #[inline]
+            pub fn as_mut_slice(&mut self) -> &mut [$t] {
+                // This is safe because we are statically bounding our slices to the size of these
+                // vectors
+                unsafe {
+                    core::slice::from_raw_parts_mut(self as *mut $n as *mut $t, 4)
+                }
+            }
+
+            #[inline]
+            pub fn as_mut_byte_slice(&mut self) -> &mut [u8] {
+                // This is safe because we are statically bounding our slices to the size of these
+                // vectors
+                unsafe {
+                    core::slice::from_raw_parts_mut(self as *mut $n as *mut u8, 4 * core::mem::size_of::<$t>())
+                }
+            }
+
+            /// Returns a constant unsafe pointer to the underlying data in the underlying type.
+            /// This function is safe because all types here are repr(C) and can be represented
+            /// as their underlying type.
+            ///
+            /// # Safety
+            ///
+            /// It is up to the caller to correctly use this pointer and its bounds.
+            #[inline]
+            pub fn as_ptr(&self) -> *const $t {
+                 self as *const $n as *const $t
+            }
+
+            /// Returns a mutable unsafe pointer to the underlying data in the underlying type.
+            /// This function is safe because all types here are repr(C) and can be represented
+            /// as their underlying type.
+            ///
+            /// # Safety
+            ///
+            /// It is up to the caller to correctly use this pointer and its bounds.
+            #[inline]
+            pub fn as_mut_ptr(&mut self) -> *mut $t {
+                self as *mut $n as *mut $t
+            }
+        }
+
+        impl From<[$t; 4]> for $n {
+            #[inline]
+            fn from(comps: [$t; 4]) -> Self {
+                Self::new(comps[0], comps[1], comps[2], comps[3])
+            }
+        }
+        impl Into<[$t; 4]> for $n {
+            #[inline]
+            fn into(self) -> [$t; 4] {
+                [self.x, self.y, self.z, self.w]
+            }
+        }
+
+        // Hashing goes through the component array so that a vector and the array it borrows as
+        // hash identically, as required by `Borrow`.
+        impl Hash for $n {
+            #[inline]
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                let comps: &[$t; 4] = self.borrow();
+                comps.hash(state);
+            }
+        }
+
+        impl Borrow<[$t; 4]> for $n {
+            #[inline]
+            fn borrow(&self) -> &[$t; 4] {
+                // This is safe because the vector is repr(C) and consists of exactly 4
+                // components of the element type
+                unsafe { &*(self as *const $n as *const [$t; 4]) }
+            }
+        }
+
+        impl From<&[$t; 4]> for $n {
+            #[inline]
+            fn from(comps: &[$t; 4]) -> Self {
+                Self::from(*comps)
+            }
+        }
+
+        impl From<&mut [$t; 4]> for $n {
+            #[inline]
+            fn from(comps: &mut [$t; 4]) -> Self {
+                Self::from(*comps)
+            }
+        }
+
+        impl From<($t, $t, $t, $t)> for $n {
+            #[inline]
+            fn from(comps: ($t, $t, $t, $t)) -> Self {
+                Self::new(comps.0, comps.1, comps.2, comps.3)
+            }
+        }
+
+        impl From<&($t, $t, $t, $t)> for $n {
+            #[inline]
+            fn from(comps: &($t, $t, $t, $t)) -> Self {
+                Self::from(*comps)
+            }
+        }
+
+        impl From<$n> for ($t, $t, $t, $t) {
+            #[inline]
+            fn from(v: $n) -> Self {
+                (v.x, v.y, v.z, v.w)
+            }
+        }
+
+        impl From<($v3t, $t)> for $n {
+            #[inline]
+            fn from(comps: ($v3t, $t)) -> Self {
+                Self::new(comps.0.x, comps.0.y, comps.0.z, comps.1)
+            }
+        }
+
+        impl From<(u8, u8, u8, u8)> for $n {
+            #[inline]
+            fn from(comps: (u8, u8, u8, u8)) -> Self {
+                $n { x: comps.0.into(), y: comps.1.into(), z: comps.2.into(), w: comps.3.into() }
+            }
+        }
+
+        impl From<(u16, u16, u16, u16)> for $n {
+            #[inline]
+            fn from(comps: (u16, u16, u16, u16)) -> Self {
+                $n { x: comps.0.into(), y: comps.1.into(), z: comps.2.into(), w: comps.3.into() }
+            }
+        }
+
+        impl TryFrom<(i64, i64, i64, i64)> for $n {
+            type Error = TryFromIntError;
+
+            #[inline]
+            fn try_from(comps: (i64, i64, i64, i64)) -> Result<Self, Self::Error> {
+                Ok($n { x: $t::try_from(comps.0)?, y: $t::try_from(comps.1)?, z: $t::try_from(comps.2)?, w: $t::try_from(comps.3)? })
+            }
+        }
+
+        impl TryFrom<(u64, u64, u64, u64)> for $n {
+            type Error = TryFromIntError;
+
+            #[inline]
+            fn try_from(comps: (u64, u64, u64, u64)) -> Result<Self, Self::Error> {
+                Ok($n { x: $t::try_from(comps.0)?, y: $t::try_from(comps.1)?, z: $t::try_from(comps.2)?, w: $t::try_from(comps.3)? })
+            }
+        }
+
+        impl Add for $n {
+            type Output = Self;
+            #[inline]
+            fn add(self, rhs: $n) -> Self {
+                $n::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z, self.w + rhs.w)
+            }
+        }
+
+        impl AddAssign for $n {
+            #[inline]
+            fn add_assign(&mut self, rhs: $n) {
+                self.x += rhs.x;
+                self.y += rhs.y;
+                self.z += rhs.z;
+                self.w += rhs.w;
+            }
+        }
+
+        impl Sub for $n {
+            type Output = Self;
+            #[inline]
+            fn sub(self, rhs: $n) -> Self {
+                $n::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z, self.w - rhs.w)
+            }
+        }
+
+        impl SubAssign for $n {
+            #[inline]
+            fn sub_assign(&mut self, rhs: $n) {
+                self.x -= rhs.x;
+                self.y -= rhs.y;
+                self.z -= rhs.z;
+                self.w -= rhs.w;
+            }
+        }
+
+        impl Mul for $n {
+            type Output = Self;
+            #[inline]
+            fn mul(self, rhs: $n) -> Self {
+                $n::new(self.x * rhs.x, self.y * rhs.y, self.z * rhs.z, self.w * rhs. w)
+            }
+        }
+
+        impl Mul<$n> for $t {
+            type Output = $n;
+            #[inline]
+            fn mul(self, rhs: $n) -> $n {
+                $n::new(self * rhs.x, self * rhs.y, self * rhs.z, self * rhs.w)
+            }
+        }
+
+        impl Mul<$t> for $n {
+            type Output = $n;
+            #[inline]
+            fn mul(self, rhs: $t) -> $n {
+                $n::new(self.x * rhs, self.y * rhs, self.z * rhs, self.w * rhs)
+            }
+        }
+
+        impl MulAssign for $n {
+            #[inline]
+            fn mul_assign(&mut self, rhs: $n) {
+                self.x *= rhs.x;
+                self.y *= rhs.y;
+                self.z *= rhs.z;
+                self.w *= rhs.w;
+            }
+        }
+
+        impl MulAssign<$t> for $n {
+            #[inline]
+            fn mul_assign(&mut self, rhs: $t) {
+                self.x *= rhs;
+                self.y *= rhs;
+                self.z *= rhs;
+                self.w *= rhs;
+            }
+        }
+
+        impl Div for $n {
+            type Output = Self;
+            #[inline]
+            fn div(self, rhs: $n) -> Self {
+                $n::new(self.x / rhs.x, self.y / rhs.y, self.z / rhs.z, self.w / rhs.w)
+            }
+        }
+
+        impl Div<$t> for $n {
+            type Output = $n;
+            #[inline]
+            fn div(self, rhs: $t) -> $n {
+                $n::new(self.x / rhs, self.y / rhs, self.z / rhs, self.w / rhs)
+            }
+        }
+
+        impl DivAssign for $n {
+            #[inline]
+            fn div_assign(&mut self, rhs: $n) {
+                self.x /= rhs.x;
+                self.y /= rhs.y;
+                self.z /= rhs.z;
+                self.w /= rhs.w;
+            }
+        }
+
+        impl DivAssign<$t> for $n {
+            #[inline]
+            fn div_assign(&mut self, rhs: $t) {
+                self.x /= rhs;
+                self.y /= rhs;
+                self.z /= rhs;
+                self.w /= rhs;
+            }
+        }
+
+        impl MulAdd for $n {
+            type Output = Self;
+
+            #[inline]
+            fn mul_add(self, a: $n, b: $n) -> Self::Output {
+                $n::mul_add(&self, a, b)
+            }
+        }
+
+        impl IntVector for $n {
+            type Scalar = $t;
+            type Array = [$t; 4];
+            const DIM: usize = 4;
+
+            #[inline]
+            fn broadcast(val: $t) -> Self {
+                $n::broadcast(val)
+            }
+
+            #[inline]
+            fn dot(&self, other: Self) -> $t {
+                $n::dot(self, other)
+            }
+
+            #[inline]
+            fn component_sum(&self) -> $t {
+                $n::component_sum(self)
+            }
+
+            #[inline]
+            fn as_slice(&self) -> &[$t] {
+                $n::as_slice(self)
+            }
+
+            #[inline]
+            fn to_array(self) -> [$t; 4] {
+                self.into()
+            }
+        }
+
+        impl Index<usize> for $n {
+            type Output = $t;
+
+            fn index(&self, index: usize) -> &Self::Output {
+                match index {
+                    0 => &self.x,
+                    1 => &self.y,
+                    2 => &self.z,
+                    3 => &self.w,
+                    _ => panic!("Invalid for vector of type: {}", core::any::type_name::<$n>()),
+                }
+            }
+        }
+
+        impl IndexMut<usize> for $n {
+            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                match index {
+                    0 => &mut self.x,
+                    1 => &mut self.y,
+                    2 => &mut self.z,
+                    3 => &mut self.w,
+                    _ => panic!("Invalid for vector of type: {}", core::any::type_name::<$n>()),
+                }
+            }
+        }
+        )+
+    }
+}
+
+vec4i!(Vec4u, Vec2u, Vec3u => u32);
+vec4i!(Vec4i, Vec2i, Vec3i => i32);
+
+impl Vec4u {
+    /// Treating `self` as an extent, returns true if `point` lies in `0..self` on every axis.
+    #[inline]
+    pub fn contains_point(&self, point: Self) -> bool {
+        point.x < self.x && point.y < self.y && point.z < self.z && point.w < self.w
+    }
+
+    /// Treating `self` as an extent, returns true if `point` lies in `0..=self` on every axis.
+    #[inline]
+    pub fn contains_point_inclusive(&self, point: Self) -> bool {
+        point.x <= self.x && point.y <= self.y && point.z <= self.z && point.w <= self.w
+    }
+
+    /// Apply a signed offset to this position, saturating each component at `0` and `u32::MAX`.
+    #[inline]
+    pub fn offset(&self, delta: Vec4i) -> Self {
+        Self::new(self.x.saturating_add_signed(delta.x), self.y.saturating_add_signed(delta.y), self.z.saturating_add_signed(delta.z), self.w.saturating_add_signed(delta.w))
+    }
+
+    /// Apply a signed offset to this position, returning `None` if any component would
+    /// underflow below zero or overflow past `u32::MAX`.
+    #[inline]
+    pub fn checked_add_signed(&self, delta: Vec4i) -> Option<Self> {
+        Some(Self::new(
+            self.x.checked_add_signed(delta.x)?,
+            self.y.checked_add_signed(delta.y)?,
+            self.z.checked_add_signed(delta.z)?,
+            self.w.checked_add_signed(delta.w)?,
+        ))
+    }
+
+    /// The largest component, i.e. the infinity norm of this vector. This is the same as
+    /// `component_max` and exists to mirror the signed vectors.
+    #[inline]
+    pub fn abs_max(&self) -> u32 {
+        self.component_max()
+    }
+
+    /// The smallest component. This is the same as `component_min` and exists to mirror the
+    /// signed vectors.
+    #[inline]
+    pub fn abs_min(&self) -> u32 {
+        self.component_min()
+    }
+
+    /// Rotate the bits of each component left by `n`, wrapping the truncated bits around to the
+    /// other end, unlike the shift operators.
+    #[inline]
+    pub fn rotate_left(self, n: u32) -> Self {
+        Self { x: self.x.rotate_left(n), y: self.y.rotate_left(n), z: self.z.rotate_left(n), w: self.w.rotate_left(n) }
+    }
+
+    /// Rotate the bits of each component right by `n`, wrapping the truncated bits around to the
+    /// other end, unlike the shift operators.
+    #[inline]
+    pub fn rotate_right(self, n: u32) -> Self {
+        Self { x: self.x.rotate_right(n), y: self.y.rotate_right(n), z: self.z.rotate_right(n), w: self.w.rotate_right(n) }
+    }
+
+    /// Rotate the bits of each component left by the matching component of `amounts`, which
+    /// like `u32::rotate_left` is taken modulo the bit width.
+    #[inline]
+    pub fn rotate_left_componentwise(self, amounts: Vec4u) -> Self {
+        Self { x: self.x.rotate_left(amounts.x), y: self.y.rotate_left(amounts.y), z: self.z.rotate_left(amounts.z), w: self.w.rotate_left(amounts.w) }
+    }
+
+    /// Rotate the bits of each component right by the matching component of `amounts`, which
+    /// like `u32::rotate_right` is taken modulo the bit width.
+    #[inline]
+    pub fn rotate_right_componentwise(self, amounts: Vec4u) -> Self {
+        Self { x: self.x.rotate_right(amounts.x), y: self.y.rotate_right(amounts.y), z: self.z.rotate_right(amounts.z), w: self.w.rotate_right(amounts.w) }
+    }
+
+    /// The wrapping difference `self - rhs`, along with a mask which is set for each component
+    /// that underflowed, i.e. where `self` is less than `rhs`.
+    #[inline]
+    pub fn overflowing_sub(&self, rhs: Self) -> (Self, BVec4) {
+        let (x, x_under) = self.x.overflowing_sub(rhs.x);
+        let (y, y_under) = self.y.overflowing_sub(rhs.y);
+        let (z, z_under) = self.z.overflowing_sub(rhs.z);
+        let (w, w_under) = self.w.overflowing_sub(rhs.w);
+        (Self { x, y, z, w }, BVec4::new(x_under, y_under, z_under, w_under))
+    }
+
+    /// Unpack four bytes from a `u32`, one per component. The byte order is little endian:
+    /// `x` is taken from the least significant byte and `w` from the most significant, so a
+    /// color packed as `0xAABBGGRR`, which is RGBA order in memory on little endian targets,
+    /// unpacks to `(r, g, b, a)`.
+    #[inline]
+    pub fn from_u32(packed: u32) -> Self {
+        let [x, y, z, w] = packed.to_le_bytes();
+        Vec4u::new(x, y, z, w)
+    }
+
+    /// Pack the components into the bytes of a `u32` in the same little endian order as
+    /// `from_u32`. Components above 255 saturate to 255 rather than being truncated.
+    #[inline]
+    pub fn to_u32(&self) -> u32 {
+        let byte = |c: u32| c.min(255) as u8;
+        u32::from_le_bytes([byte(self.x), byte(self.y), byte(self.z), byte(self.w)])
+    }
+
+    /// The component-wise bitwise OR of every vector in `vecs`, starting from zero, so an
+    /// empty slice gives zero.
+    #[inline]
+    pub fn fold_or(vecs: &[Vec4u]) -> Vec4u {
+        let mut acc = Vec4u::zero();
+        for v in vecs {
+            acc.x |= v.x;
+            acc.y |= v.y;
+            acc.z |= v.z;
+            acc.w |= v.w;
+        }
+        acc
+    }
+
+    /// The component-wise bitwise AND of every vector in `vecs`, starting from all ones, so an
+    /// empty slice gives `u32::MAX` in every component.
+    #[inline]
+    pub fn fold_and(vecs: &[Vec4u]) -> Vec4u {
+        let mut acc = Vec4u::broadcast(u32::MAX);
+        for v in vecs {
+            acc.x &= v.x;
+            acc.y &= v.y;
+            acc.z &= v.z;
+            acc.w &= v.w;
+        }
+        acc
+    }
+
+    /// Map each component from `0..=u32::MAX` to `0.0..=1.0` by dividing by `u32::MAX`, as is
+    /// done to normalize integer colors. The division is done in `f64` before rounding to `f32`.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn to_normalized_f32(&self) -> crate::vec::Vec4 {
+        let norm = |c: u32| (f64::from(c) / f64::from(u32::MAX)) as f32;
+        crate::vec::Vec4::new(norm(self.x), norm(self.y), norm(self.z), norm(self.w))
+    }
+}
+
+impl Vec4i {
+    #[inline]
+    pub fn flip_x(&self) -> Self {
+        Self { x: -self.x, ..*self }
+    }
+
+    #[inline]
+    pub fn flip_y(&self) -> Self {
+        Self { y: -self.y, ..*self }
+    }
+
+    #[inline]
+    pub fn flip_z(&self) -> Self {
+        Self { z: -self.z, ..*self }
+    }
+
+    #[inline]
+    pub fn flip_w(&self) -> Self {
+        Self { w: -self.w, ..*self }
+    }
+
+    /// Pack this vector into the signed 10-10-10-2 format used for vertex normals and tangents,
+    /// laid out like `GL_INT_2_10_10_10_REV`: `x` in bits 0-9, `y` in bits 10-19, `z` in bits
+    /// 20-29 and `w` in bits 30-31, each in two's complement.
+    ///
+    /// `x`, `y` and `z` are clamped to the signed 10-bit range `-512..=511`, and `w` to the
+    /// signed 2-bit range `-2..=1`.
+    #[inline]
+    pub fn pack_1010102(&self) -> u32 {
+        let pack = |c: i32, bits: u32, shift: u32| {
+            let half = 1 << (bits - 1);
+            ((c.clamp(-half, half - 1) as u32) & ((1 << bits) - 1)) << shift
+        };
+        pack(self.x, 10, 0) | pack(self.y, 10, 10) | pack(self.z, 10, 20) | pack(self.w, 2, 30)
+    }
+
+    /// Unpack a vector from the signed 10-10-10-2 format, sign-extending each component. This
+    /// is the inverse of `pack_1010102` for components within range.
+    #[inline]
+    pub fn unpack_1010102(packed: u32) -> Self {
+        let unpack = |bits: u32, shift: u32| ((packed << (32 - bits - shift)) as i32) >> (32 - bits);
+        Vec4i::new(unpack(10, 0), unpack(10, 10), unpack(10, 20), unpack(2, 30))
+    }
+}
+
+impl From<Vec3u> for Vec2u {
+    #[inline]
+    fn from(vec: Vec3u) -> Self {
+        Self {
+            x: vec.x,
+            y: vec.y,
+        }
+    }
+}
+
+
+impl From<Vec3u> for Vec4u {
+    #[inline]
+    fn from(vec: Vec3u) -> Self {
+        Self {
+            x: vec.x,
+            y: vec.y,
+            z: vec.z,
+            w: 0,
+        }
+    }
+}
+
+impl From<Vec4u> for Vec3u {
     #[inline]
     fn from(vec: Vec4u) -> Self {
         Self {
@@ -1367,38 +4682,1847 @@ impl From<Vec4u> for Vec3u {
             z: vec.z,
         }
     }
-}
+}
+
+impl From<Vec3i> for Vec2i {
+    #[inline]
+    fn from(vec: Vec3i) -> Self {
+        Self {
+            x: vec.x,
+            y: vec.y,
+        }
+    }
+}
+
+
+impl From<Vec3i> for Vec4i {
+    #[inline]
+    fn from(vec: Vec3i) -> Self {
+        Self {
+            x: vec.x,
+            y: vec.y,
+            z: vec.z,
+            w: 0,
+        }
+    }
+}
+
+impl From<Vec4i> for Vec3i {
+    #[inline]
+    fn from(vec: Vec4i) -> Self {
+        Self {
+            x: vec.x,
+            y: vec.y,
+            z: vec.z,
+        }
+    }
+}
+
+macro_rules! int_sign_casts {
+    ($($from:ident => $to:ident { $($field:ident),+ }),+) => {
+        $(
+        impl TryFrom<$from> for $to {
+            type Error = TryFromIntError;
+
+            #[inline]
+            fn try_from(vec: $from) -> Result<Self, Self::Error> {
+                Ok($to { $($field: TryFrom::try_from(vec.$field)?),+ })
+            }
+        }
+        )+
+    };
+}
+
+int_sign_casts!(
+    Vec2i => Vec2u { x, y },
+    Vec3i => Vec3u { x, y, z },
+    Vec4i => Vec4u { x, y, z, w },
+    Vec2u => Vec2i { x, y },
+    Vec3u => Vec3i { x, y, z },
+    Vec4u => Vec4i { x, y, z, w }
+);
+
+macro_rules! int_componentwise_shifts {
+    ($($n:ident => $un:ident { $($field:ident),+ }),+) => {
+        $(
+        impl $n {
+            /// Shift each component left by the matching component of `shifts`. As with
+            /// `wrapping_shl`, shift amounts are taken modulo the bit width, so a shift of 32
+            /// leaves the component unchanged.
+            #[inline]
+            pub fn shl_componentwise(self, shifts: $un) -> Self {
+                $n { $($field: self.$field.wrapping_shl(shifts.$field)),+ }
+            }
+
+            /// Shift each component right by the matching component of `shifts`. As with
+            /// `wrapping_shr`, shift amounts are taken modulo the bit width, so a shift of 32
+            /// leaves the component unchanged.
+            #[inline]
+            pub fn shr_componentwise(self, shifts: $un) -> Self {
+                $n { $($field: self.$field.wrapping_shr(shifts.$field)),+ }
+            }
+        }
+        )+
+    };
+}
+
+int_componentwise_shifts!(
+    Vec2u => Vec2u { x, y },
+    Vec3u => Vec3u { x, y, z },
+    Vec4u => Vec4u { x, y, z, w },
+    Vec2i => Vec2u { x, y },
+    Vec3i => Vec3u { x, y, z },
+    Vec4i => Vec4u { x, y, z, w }
+);
+
+macro_rules! int_radix_fmt {
+    ($($n:ident { $first:ident $(, $rest:ident)* }),+) => {
+        $(
+        int_radix_fmt!(@impl $n, Binary, $first $(, $rest)*);
+        int_radix_fmt!(@impl $n, Octal, $first $(, $rest)*);
+        int_radix_fmt!(@impl $n, LowerHex, $first $(, $rest)*);
+        int_radix_fmt!(@impl $n, UpperHex, $first $(, $rest)*);
+        )+
+    };
+    (@impl $n:ident, $fmt:ident, $first:ident $(, $rest:ident)*) => {
+        /// Formats the vector as a parenthesized list of components, passing the formatter
+        /// through to each one so that flags like `#` and width are honored.
+        impl core::fmt::$fmt for $n {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str("(")?;
+                core::fmt::$fmt::fmt(&self.$first, f)?;
+                $(
+                f.write_str(", ")?;
+                core::fmt::$fmt::fmt(&self.$rest, f)?;
+                )*
+                f.write_str(")")
+            }
+        }
+    };
+}
+
+int_radix_fmt!(
+    Vec2u { x, y },
+    Vec3u { x, y, z },
+    Vec4u { x, y, z, w },
+    Vec2i { x, y },
+    Vec3i { x, y, z },
+    Vec4i { x, y, z, w }
+);
+
+macro_rules! int_from_float_saturating {
+    ($($n:ident => $t:ident, $fv:ident { $($field:ident),+ }),+) => {
+        $(
+        #[cfg(feature = "std")]
+        impl $n {
+            /// Convert a float vector by truncating each component towards zero, saturating
+            /// components which are out of range for the element type.
+            ///
+            /// Infinities saturate to the minimum or maximum of the element type, and NaN
+            /// components are mapped to `0`.
+            #[inline]
+            pub fn from_f32_saturating(v: crate::vec::$fv) -> Self {
+                // `as` casts from floats to integers saturate and map NaN to zero.
+                $n { $($field: v.$field as $t),+ }
+            }
+        }
+        )+
+    };
+}
+
+int_from_float_saturating!(
+    Vec2u => u32, Vec2 { x, y },
+    Vec3u => u32, Vec3 { x, y, z },
+    Vec4u => u32, Vec4 { x, y, z, w },
+    Vec2i => i32, Vec2 { x, y },
+    Vec3i => i32, Vec3 { x, y, z },
+    Vec4i => i32, Vec4 { x, y, z, w }
+);
+
+macro_rules! int_signed_ops {
+    ($($n:ident => $un:ident, $bv:ident { $($field:ident),+ }),+) => {
+        $(
+        impl $n {
+            /// Scale this vector so that its magnitude is approximately `1 << shift`, giving a
+            /// reproducible fixed-point direction without any floating point math.
+            ///
+            /// Each component is rounded to the nearest integer, so the magnitude of the result
+            /// differs from `1 << shift` by at most 1. A zero vector is returned unchanged.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `shift` is 31 or greater, as the result would not fit in an `i32`.
+            #[inline]
+            pub fn fixed_direction(&self, shift: u32) -> Self {
+                assert!(shift < 31, "fixed_direction shift must be less than 31");
+                let mut v = *self;
+                scale_to_magnitude(v.as_mut_slice(), 1 << shift);
+                v
+            }
+
+            /// Negate the components for which `mask` is set, leaving the others untouched.
+            #[inline]
+            pub fn flip_axes(&self, mask: $bv) -> Self {
+                $n { $($field: if mask.$field { -self.$field } else { self.$field }),+ }
+            }
+
+            /// The component-wise absolute value as an unsigned vector, which unlike `abs` cannot
+            /// overflow for components equal to `i32::MIN`.
+            #[inline]
+            pub fn unsigned_abs(&self) -> $un {
+                $un { $($field: self.$field.unsigned_abs()),+ }
+            }
+
+            /// The largest absolute value of any component, i.e. the infinity norm of this vector.
+            ///
+            /// This is returned as a `u32` so that a component equal to `i32::MIN` does not overflow.
+            #[inline]
+            pub fn abs_max(&self) -> u32 {
+                self.unsigned_abs().component_max()
+            }
+
+            /// The smallest absolute value of any component.
+            #[inline]
+            pub fn abs_min(&self) -> u32 {
+                self.unsigned_abs().component_min()
+            }
+
+            /// Clamp each coordinate to `0..size`, like a `CLAMP_TO_EDGE` sampler addressing a texture
+            /// of the given size. Every component of `size` must be positive.
+            #[inline]
+            pub fn wrap_clamp(&self, size: Self) -> Self {
+                $n { $($field: self.$field.clamp(0, size.$field - 1)),+ }
+            }
+
+            /// Wrap each coordinate into `0..size`, like a `REPEAT` sampler addressing a texture of
+            /// the given size. Every component of `size` must be positive.
+            #[inline]
+            pub fn wrap_repeat(&self, size: Self) -> Self {
+                $n { $($field: self.$field.rem_euclid(size.$field)),+ }
+            }
+
+            /// Reflect each coordinate back and forth over `0..size`, like a `MIRRORED_REPEAT` sampler
+            /// addressing a texture of the given size, so that a size of 3 maps `-3..6` to
+            /// `2, 1, 0, 0, 1, 2, 2, 1, 0`. Every component of `size` must be positive.
+            #[inline]
+            pub fn wrap_mirror(&self, size: Self) -> Self {
+                $n { $($field: mirror_coord(self.$field, size.$field)),+ }
+            }
+
+            /// Scale this vector up to a magnitude of approximately `min` if it is currently shorter,
+            /// preserving its direction. Vectors which are already at least `min` long are returned
+            /// unchanged.
+            ///
+            /// As with `fixed_direction`, each component of a scaled vector is rounded to the nearest
+            /// integer. A zero vector has no direction to preserve, so it is always returned as zero.
+            #[inline]
+            pub fn clamp_length_min(&self, min: i32) -> Self {
+                let min = min.max(0);
+                let mut v = *self;
+                if self.distance_sq_to(Self::zero()) < (min as u128) * (min as u128) {
+                    scale_to_magnitude(v.as_mut_slice(), i64::from(min));
+                }
+                v
+            }
+
+            /// The component-wise absolute value computed without branches, using the
+            /// `(x ^ (x >> 31)) - (x >> 31)` trick. This matches `i32::wrapping_abs`, so a component
+            /// equal to `i32::MIN` is left unchanged.
+            #[inline]
+            pub fn abs_branchless(&self) -> Self {
+                let abs = |c: i32| {
+                    let mask = c >> 31;
+                    (c ^ mask).wrapping_sub(mask)
+                };
+                $n { $($field: abs(self.$field)),+ }
+            }
+
+            /// A unit step (`1` or `-1`) along the axis with the greatest absolute component, with
+            /// all other components zero. Ties are broken as in `dominant_signed_axis`, and a zero
+            /// vector returns zero.
+            #[inline]
+            pub fn dominant_axis_step(&self) -> Self {
+                if *self == Self::zero() {
+                    return Self::zero();
+                }
+                let (axis, sign) = self.dominant_signed_axis();
+                Self::zero().with_component(axis, sign)
+            }
+
+            /// The index of the axis with the greatest absolute component, along with the sign of
+            /// that component as `1` or `-1`. Ties are broken in favor of the earliest axis (`x`
+            /// before `y` and so on), like `dominant_axis_step`. A zero vector returns `(0, 1)`.
+            #[inline]
+            pub fn dominant_signed_axis(&self) -> (usize, i32) {
+                let comps = self.as_slice();
+                let mut axis = 0;
+                for i in 1..comps.len() {
+                    if comps[i].unsigned_abs() > comps[axis].unsigned_abs() {
+                        axis = i;
+                    }
+                }
+                (axis, if comps[axis] < 0 { -1 } else { 1 })
+            }
+
+            /// The component-wise negation, or `None` if any component is `i32::MIN`, whose negation
+            /// would overflow.
+            #[inline]
+            pub fn checked_neg(&self) -> Option<Self> {
+                Some($n { $($field: self.$field.checked_neg()?),+ })
+            }
+        }
+        )+
+    };
+}
+
+int_signed_ops!(
+    Vec2i => Vec2u, BVec2 { x, y },
+    Vec3i => Vec3u, BVec3 { x, y, z },
+    Vec4i => Vec4u, BVec4 { x, y, z, w }
+);
+
+macro_rules! int_aabbs {
+    ($($n:ident => $vt:ident),+) => {
+        $(
+        /// An axis-aligned bounding box over integer points, described by its inclusive
+        /// `min` and `max` corners.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[repr(C)]
+        pub struct $n {
+            pub min: $vt,
+            pub max: $vt,
+        }
+
+        impl $n {
+            #[inline]
+            pub fn new(min: $vt, max: $vt) -> Self {
+                $n { min, max }
+            }
+
+            /// Create a bounding box containing only the given point.
+            #[inline]
+            pub fn from_point(point: $vt) -> Self {
+                Self::new(point, point)
+            }
+
+            /// Create the smallest bounding box containing every point in `points`,
+            /// or `None` if there are no points.
+            #[inline]
+            pub fn from_points<I>(points: I) -> Option<Self>
+                where I: IntoIterator<Item = $vt>
+            {
+                let mut points = points.into_iter();
+                let mut aabb = Self::from_point(points.next()?);
+                for point in points {
+                    aabb.expand(point);
+                }
+                Some(aabb)
+            }
+
+            /// Grow this bounding box so that it contains `point`.
+            #[inline]
+            pub fn expand(&mut self, point: $vt) {
+                self.min = self.min.min_by_component(point);
+                self.max = self.max.max_by_component(point);
+            }
+
+            #[inline]
+            pub fn expanded(mut self, point: $vt) -> Self {
+                self.expand(point);
+                self
+            }
+
+            /// The extent of the box, `max - min`.
+            #[inline]
+            pub fn size(&self) -> $vt {
+                self.max - self.min
+            }
+
+            /// Returns true if `point` lies inside the box, including on its boundary.
+            #[inline]
+            pub fn contains(&self, point: $vt) -> bool {
+                self.min.max_by_component(point) == point && self.max.min_by_component(point) == point
+            }
+        }
+        )+
+    };
+}
+
+int_aabbs!(IntAabb2 => Vec2i, IntAabb3 => Vec3i);
+
+/// The orientation of three points, as returned by `Vec2i::turn`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Turn {
+    /// A counter-clockwise turn (with the y axis pointing up).
+    Left,
+    /// A clockwise turn (with the y axis pointing up).
+    Right,
+    /// The three points lie on a single line.
+    Collinear,
+}
+
+/// One of the four orthogonal directions on a 2d grid, with north pointing along `+y`
+/// and east along `+x`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction4 {
+    /// Every direction, clockwise starting from north.
+    pub const ALL: [Direction4; 4] = [Direction4::North, Direction4::East, Direction4::South, Direction4::West];
+
+    /// The unit offset of a single step in this direction.
+    #[inline]
+    pub fn to_offset(&self) -> Vec2i {
+        match self {
+            Direction4::North => Vec2i::new(0, 1),
+            Direction4::East => Vec2i::new(1, 0),
+            Direction4::South => Vec2i::new(0, -1),
+            Direction4::West => Vec2i::new(-1, 0),
+        }
+    }
+}
+
+/// One of the eight orthogonal and diagonal directions on a 2d grid, with north pointing
+/// along `+y` and east along `+x`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise starting from north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// The offset of a single step in this direction, with each component `-1`, `0` or `1`.
+    #[inline]
+    pub fn to_offset(&self) -> Vec2i {
+        match self {
+            Direction8::North => Vec2i::new(0, 1),
+            Direction8::NorthEast => Vec2i::new(1, 1),
+            Direction8::East => Vec2i::new(1, 0),
+            Direction8::SouthEast => Vec2i::new(1, -1),
+            Direction8::South => Vec2i::new(0, -1),
+            Direction8::SouthWest => Vec2i::new(-1, -1),
+            Direction8::West => Vec2i::new(-1, 0),
+            Direction8::NorthWest => Vec2i::new(-1, 1),
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    #[inline]
+    fn from(dir: Direction4) -> Self {
+        match dir {
+            Direction4::North => Direction8::North,
+            Direction4::East => Direction8::East,
+            Direction4::South => Direction8::South,
+            Direction4::West => Direction8::West,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    pub fn split_chunk_negative() {
+        let size = Vec3i::new(16, 16, 16);
+        for &c in [-33, -17, -16, -1, 0, 1, 15, 16, 31].iter() {
+            let (chunk, local) = Vec3i::new(c, -c, c).split_chunk(size);
+            for i in 0..3 {
+                assert!(local[i] >= 0 && local[i] < 16);
+                assert_eq!(chunk[i] * 16 + local[i], [c, -c, c][i]);
+            }
+        }
+        assert_eq!(
+            Vec3i::new(-1, -16, -17).split_chunk(size),
+            (Vec3i::new(-1, -1, -2), Vec3i::new(15, 0, 15))
+        );
+        assert_eq!(
+            Vec3u::new(33, 0, 15).split_chunk(Vec3u::broadcast(16)),
+            (Vec3u::new(2, 0, 0), Vec3u::new(1, 0, 15))
+        );
+    }
+
+    #[test]
+    pub fn fixed_direction_magnitude() {
+        let shift = 16;
+        let target = (1i64 << shift) as f64;
+        for v in [Vec3i::new(1, 1, 1), Vec3i::new(-3, 7, 0), Vec3i::new(i32::MAX, -5, 12)].iter() {
+            let d = v.fixed_direction(shift);
+            let mag = ((d.x as f64).powi(2) + (d.y as f64).powi(2) + (d.z as f64).powi(2)).sqrt();
+            assert!((mag - target).abs() <= 1.0, "{:?} -> {:?} ({})", v, d, mag);
+        }
+        let d = Vec2i::new(0, -5).fixed_direction(10);
+        assert_eq!(d, Vec2i::new(0, -1024));
+        assert_eq!(Vec4i::zero().fixed_direction(8), Vec4i::zero());
+    }
+
+    #[test]
+    pub fn flip_axes_combinations() {
+        let v = Vec3i::new(1, -2, 3);
+        assert_eq!(v.flip_axes(BVec3::new(true, false, false)), Vec3i::new(-1, -2, 3));
+        assert_eq!(v.flip_axes(BVec3::new(false, true, true)), Vec3i::new(1, 2, -3));
+        assert_eq!(v.flip_axes(BVec3::broadcast(false)), v);
+        assert_eq!(v.flip_axes(BVec3::broadcast(true)), Vec3i::new(-1, 2, -3));
+        assert_eq!(v.flip_y(), Vec3i::new(1, 2, 3));
+        assert_eq!(v.flip_x().flip_z(), v.flip_axes(BVec3::new(true, false, true)));
+        assert_eq!(Vec2i::new(4, 5).flip_axes(BVec2::new(false, true)), Vec2i::new(4, -5));
+        assert_eq!(Vec4i::new(1, 2, 3, 4).flip_w(), Vec4i::new(1, 2, 3, -4));
+    }
+
+    #[test]
+    pub fn int_aabb_from_points() {
+        let points = [Vec3i::new(1, -2, 3), Vec3i::new(-4, 5, 0), Vec3i::new(2, 2, -6)];
+        let aabb = IntAabb3::from_points(points.iter().copied()).unwrap();
+        assert_eq!(aabb.min, Vec3i::new(-4, -2, -6));
+        assert_eq!(aabb.max, Vec3i::new(2, 5, 3));
+        assert_eq!(aabb.size(), Vec3i::new(6, 7, 9));
+        assert!(aabb.contains(aabb.min));
+        assert!(aabb.contains(aabb.max));
+        assert!(aabb.contains(Vec3i::new(-4, 5, 3)));
+        assert!(!aabb.contains(Vec3i::new(3, 0, 0)));
+        assert!(!aabb.contains(Vec3i::new(0, -3, 0)));
+        assert!(IntAabb2::from_points(core::iter::empty()).is_none());
+        let aabb = IntAabb2::from_point(Vec2i::zero()).expanded(Vec2i::new(3, -1));
+        assert_eq!(aabb, IntAabb2::new(Vec2i::new(0, -1), Vec2i::new(3, 0)));
+    }
+
+    #[test]
+    pub fn contains_point_boundaries() {
+        let extent = Vec2u::new(4, 3);
+        assert!(extent.contains_point(Vec2u::new(0, 0)));
+        assert!(extent.contains_point(Vec2u::new(3, 2)));
+        assert!(!extent.contains_point(Vec2u::new(4, 2)));
+        assert!(!extent.contains_point(Vec2u::new(3, 3)));
+        assert!(extent.contains_point_inclusive(Vec2u::new(4, 3)));
+        assert!(!extent.contains_point_inclusive(Vec2u::new(5, 0)));
+        assert!(!Vec3u::zero().contains_point(Vec3u::zero()));
+        assert!(Vec3u::zero().contains_point_inclusive(Vec3u::zero()));
+        assert!(!Vec4u::broadcast(2u32).contains_point(Vec4u::new(0u32, 0, 0, 2)));
+    }
+
+    #[test]
+    pub fn iter_ray_sequence() {
+        let cells: Vec<_> = Vec3i::iter_ray(Vec3i::new(1, 0, -1), Vec3i::new(2, -1, 0), 4).collect();
+        assert_eq!(
+            cells,
+            vec![Vec3i::new(1, 0, -1), Vec3i::new(3, -1, -1), Vec3i::new(5, -2, -1), Vec3i::new(7, -3, -1)]
+        );
+        assert_eq!(Vec3i::iter_ray(Vec3i::zero(), Vec3i::one(), 0).count(), 0);
+    }
+
+    #[test]
+    pub fn unsigned_abs_min() {
+        assert_eq!(Vec2i::new(i32::MIN, -3).unsigned_abs(), Vec2u::new(1 << 31, 3));
+        assert_eq!(Vec3i::new(-1, 0, i32::MAX).unsigned_abs(), Vec3u::new(1, 0, i32::MAX as u32));
+        assert_eq!(
+            Vec4i::new(i32::MIN, i32::MIN, 5, -5).unsigned_abs(),
+            Vec4u::new(1u32 << 31, 1 << 31, 5, 5)
+        );
+    }
+
+    #[test]
+    pub fn turn_orientation() {
+        let a = Vec2i::new(0, 0);
+        let b = Vec2i::new(4, 0);
+        assert_eq!(Vec2i::turn(a, b, Vec2i::new(5, 1)), Turn::Left);
+        assert_eq!(Vec2i::turn(a, b, Vec2i::new(5, -1)), Turn::Right);
+        assert_eq!(Vec2i::turn(a, b, Vec2i::new(-8, 0)), Turn::Collinear);
+        let big = Vec2i::new(i32::MAX, i32::MAX);
+        assert_eq!(Vec2i::turn(Vec2i::new(i32::MIN, i32::MIN), big, Vec2i::new(i32::MIN, i32::MAX)), Turn::Left);
+    }
+
+    #[test]
+    pub fn min_max_tracked() {
+        let a = Vec3i::new(1, 5, 3);
+        let b = Vec3i::new(2, -1, 3);
+        assert_eq!(a.min_by_component_tracked(b), (Vec3i::new(1, -1, 3), BVec3::new(false, true, false)));
+        assert_eq!(a.max_by_component_tracked(b), (Vec3i::new(2, 5, 3), BVec3::new(true, false, false)));
+        let (v, mask) = Vec2u::new(4, 4).min_by_component_tracked(Vec2u::new(4, 4));
+        assert_eq!(v, Vec2u::new(4, 4));
+        assert!(!mask.any());
+    }
+
+    #[test]
+    pub fn spatial_hash_distribution() {
+        assert_eq!(Vec3i::new(1, 2, 3).spatial_hash(), Vec3i::new(1, 2, 3).spatial_hash());
+        assert_eq!(Vec2u::new(7, 9).spatial_hash(), Vec2i::new(7, 9).spatial_hash());
+        let mut buckets = [0usize; 64];
+        let mut hashes = Vec::new();
+        for x in -4..4 {
+            for y in -4..4 {
+                for z in -4..4 {
+                    let hash = Vec3i::new(x, y, z).spatial_hash();
+                    buckets[(hash % 64) as usize] += 1;
+                    hashes.push(hash);
+                }
+            }
+        }
+        hashes.sort();
+        hashes.dedup();
+        assert_eq!(hashes.len(), 512);
+        assert!(buckets.iter().all(|&count| count > 0 && count <= 24));
+    }
 
-impl From<Vec3i> for Vec2i {
-    #[inline]
-    fn from(vec: Vec3i) -> Self {
-        Self {
-            x: vec.x,
-            y: vec.y,
+    #[test]
+    pub fn float_array_promotion() {
+        let v = Vec3i::new(i32::MIN, i32::MAX, -7);
+        assert_eq!(v.to_f64_array(), [-2147483648.0, 2147483647.0, -7.0]);
+        assert_eq!(Vec4u::new(u32::MAX, 0, 1, 16_777_216).to_f64_array(), [4294967295.0, 0.0, 1.0, 16777216.0]);
+        assert_eq!(Vec2i::new(-3, 16_777_216).to_f32_array(), [-3.0, 16777216.0]);
+    }
+
+    #[test]
+    pub fn adjacency() {
+        let a = Vec2i::new(3, 3);
+        assert!(!a.is_adjacent_4(a) && !a.is_adjacent_8(a));
+        assert!(a.is_adjacent_4(Vec2i::new(3, 4)) && a.is_adjacent_8(Vec2i::new(3, 4)));
+        assert!(!a.is_adjacent_4(Vec2i::new(2, 2)) && a.is_adjacent_8(Vec2i::new(2, 2)));
+        assert!(!a.is_adjacent_4(Vec2i::new(5, 3)) && !a.is_adjacent_8(Vec2i::new(5, 3)));
+        let b = Vec3i::new(0, 0, 0);
+        assert!(b.is_adjacent_4(Vec3i::new(0, 0, -1)));
+        assert!(b.is_adjacent_8(Vec3i::new(1, -1, 1)) && !b.is_adjacent_4(Vec3i::new(1, -1, 1)));
+        assert!(!Vec2i::new(i32::MIN, 0).is_adjacent_8(Vec2i::new(i32::MAX, 0)));
+    }
+
+    #[test]
+    pub fn align_to_multiples() {
+        let v = Vec3i::new(-5, 7, 12);
+        let m = Vec3i::new(4, 3, 0);
+        assert_eq!(v.align_down(m), Vec3i::new(-8, 6, 12));
+        assert_eq!(v.align_up(m), Vec3i::new(-4, 9, 12));
+        assert_eq!(Vec2i::new(-8, 9).align_down(Vec2i::new(4, -3)), Vec2i::new(-8, 9));
+        assert_eq!(Vec2i::new(-7, 10).align_up(Vec2i::new(-4, -3)), Vec2i::new(-4, 12));
+        assert_eq!(Vec4u::new(5u32, 6, 0, 13).align_up(Vec4u::new(5u32, 4, 7, 6)), Vec4u::new(5u32, 8, 0, 18));
+        assert_eq!(Vec4u::new(5u32, 6, 0, 13).align_down(Vec4u::new(5u32, 4, 7, 6)), Vec4u::new(5u32, 4, 0, 12));
+    }
+
+    #[test]
+    pub fn borrow_array_lookup() {
+        use std::collections::{HashMap, HashSet};
+
+        let mut map = HashMap::new();
+        map.insert(Vec3i::new(1, -2, 3), "a");
+        map.insert(Vec3i::new(0, 0, 0), "b");
+        assert_eq!(map.get(&[1, -2, 3]), Some(&"a"));
+        assert_eq!(map.get(&[0, 0, 0]), Some(&"b"));
+        assert_eq!(map.get(&[3, -2, 1]), None);
+        let set: HashSet<Vec2u> = [Vec2u::new(4, 5)].iter().copied().collect();
+        assert!(set.contains(&[4u32, 5]));
+    }
+
+    #[test]
+    pub fn ring_and_spiral() {
+        let center = Vec2i::new(5, -2);
+        let ring: Vec<_> = Vec2i::iter_ring(center, 1).collect();
+        assert_eq!(ring.len(), 8);
+        assert_eq!(ring[0], Vec2i::new(4, -3));
+        assert!(ring.iter().all(|c| (*c - center).map(i32::abs).component_max() == 1));
+        assert_eq!(Vec2i::iter_ring(center, 3).count(), 24);
+        assert_eq!(ring, Vec2i::iter_ring(center, 1).collect::<Vec<_>>());
+        let mut spiral: Vec<_> = Vec2i::iter_spiral(center, 2).collect();
+        assert_eq!(spiral.len(), 25);
+        assert_eq!(spiral[0], center);
+        spiral.sort_by_key(|c| (c.x, c.y));
+        spiral.dedup();
+        assert_eq!(spiral.len(), 25);
+        assert_eq!(Vec2i::iter_ring(center, -1).count(), 0);
+    }
+
+    #[test]
+    pub fn offset_saturates() {
+        let p = Vec2u::new(3, 10);
+        assert_eq!(p.offset(Vec2i::new(-5, 2)), Vec2u::new(0, 12));
+        assert_eq!(p.offset(Vec2i::new(-3, -10)), Vec2u::zero());
+        assert_eq!(Vec3u::new(u32::MAX - 1, 0, 7).offset(Vec3i::new(5, -1, -7)), Vec3u::new(u32::MAX, 0, 0));
+    }
+
+    #[test]
+    pub fn checked_add_signed_bounds() {
+        let p = Vec3u::new(3, 10, 0);
+        assert_eq!(p.checked_add_signed(Vec3i::new(-3, 2, 5)), Some(Vec3u::new(0, 12, 5)));
+        assert_eq!(p.checked_add_signed(Vec3i::new(0, 0, -1)), None);
+        assert_eq!(Vec2u::new(u32::MAX, 0).checked_add_signed(Vec2i::new(1, 0)), None);
+    }
+
+    #[test]
+    pub fn tuple_extend() {
+        assert_eq!(Vec3i::from((Vec2i::new(1, -2), 7)), Vec3i::new(1, -2, 7));
+        assert_eq!(Vec4u::from((Vec3u::new(1, 2, 3), 4)), Vec4u::new(1u32, 2, 3, 4));
+        let v: Vec4i = (Vec3i::new(-1, 0, 1), 1).into();
+        assert_eq!(v, Vec3i::new(-1, 0, 1).into_homogeneous_point());
+    }
+
+    #[test]
+    pub fn byte_round_trip() {
+        let v = Vec3i::new(1, -2, 0x0102_0304);
+        let le = v.to_le_bytes();
+        assert_eq!(&le[..8], &[1, 0, 0, 0, 0xfe, 0xff, 0xff, 0xff]);
+        assert_eq!(&v.to_be_bytes()[8..], &[1, 2, 3, 4]);
+        assert_eq!(Vec3i::from_le_bytes(le), v);
+        assert_eq!(Vec3i::from_be_bytes(v.to_be_bytes()), v);
+        assert_ne!(Vec3i::from_be_bytes(le), v);
+        let w = Vec4u::new(u32::MAX, 0, 7, 1 << 31);
+        assert_eq!(Vec4u::from_be_bytes(w.to_be_bytes()), w);
+        assert_eq!(Vec2u::from_le_bytes(Vec2u::new(9, 10).to_le_bytes()), Vec2u::new(9, 10));
+    }
+
+    #[test]
+    pub fn rotate_components_cycle() {
+        let v = Vec3i::new(1, 2, 3);
+        assert_eq!(v.rotate_components(), Vec3i::new(2, 3, 1));
+        assert_eq!(v.rotate_components_rev(), Vec3i::new(3, 1, 2));
+        assert_eq!(v.rotate_components().rotate_components().rotate_components(), v);
+        assert_eq!(v.rotate_components().rotate_components_rev(), v);
+        let w = Vec4u::new(1u32, 2, 3, 4);
+        assert_eq!(w.rotate_components(), Vec4u::new(2u32, 3, 4, 1));
+        assert_eq!(w.rotate_components_rev(), Vec4u::new(4u32, 1, 2, 3));
+        assert_eq!(w.rotate_components().rotate_components().rotate_components().rotate_components(), w);
+    }
+
+    #[test]
+    pub fn component_by_axis() {
+        let v = Vec3i::new(4, -5, 6);
+        assert_eq!(v.component(0), Some(4));
+        assert_eq!(v.component(1), Some(-5));
+        assert_eq!(v.component(2), Some(6));
+        assert_eq!(v.component(3), None);
+        assert_eq!(v.with_component(1, 9), Vec3i::new(4, 9, 6));
+        assert_eq!(Vec4u::zero().with_component(3, 1), Vec4u::unit_w());
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn with_component_invalid_axis() {
+        Vec2u::zero().with_component(2, 1);
+    }
+
+    #[test]
+    pub fn dominant_axis() {
+        assert_eq!(Vec3i::new(1, -7, 3).dominant_axis_step(), Vec3i::new(0, -1, 0));
+        assert_eq!(Vec2i::new(5, 4).dominant_axis_step(), Vec2i::new(1, 0));
+        assert_eq!(Vec3i::new(-2, 2, -2).dominant_axis_step(), Vec3i::new(-1, 0, 0));
+        assert_eq!(Vec4i::new(0, 3, -3, 1).dominant_axis_step(), Vec4i::new(0, 1, 0, 0));
+        assert_eq!(Vec4i::new(0, 0, 0, i32::MIN).dominant_axis_step(), Vec4i::new(0, 0, 0, -1));
+        assert_eq!(Vec3i::zero().dominant_axis_step(), Vec3i::zero());
+    }
+
+    #[test]
+    pub fn split_halves_round_trip() {
+        let v = Vec4i::new(1, -2, 3, -4);
+        assert_eq!(v.zw(), Vec2i::new(3, -4));
+        let (lo, hi) = v.split_halves();
+        assert_eq!((lo, hi), (Vec2i::new(1, -2), Vec2i::new(3, -4)));
+        assert_eq!(Vec4i::from_halves(lo, hi), v);
+        let u = Vec4u::new(5u32, 6, 7, 8);
+        let (lo, hi) = u.split_halves();
+        assert_eq!(Vec4u::from_halves(lo, hi), u);
+    }
+
+    #[test]
+    pub fn axis_swaps() {
+        let y_up = Vec3i::new(1, 2, 3);
+        let z_up = y_up.with_yz_swapped();
+        assert_eq!(z_up, Vec3i::new(1, 3, 2));
+        assert_eq!(z_up.with_yz_swapped(), y_up);
+        assert_eq!(y_up.permute_axes([0, 2, 1]), z_up);
+        assert_eq!(y_up.yzx(), y_up.permute_axes([1, 2, 0]));
+        assert_eq!(y_up.zxy(), y_up.permute_axes([2, 0, 1]));
+        assert_eq!(Vec3u::new(7, 8, 9).permute_axes([0, 1, 2]), Vec3u::new(7, 8, 9));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn permute_axes_invalid() {
+        Vec3i::zero().permute_axes([0, 0, 1]);
+    }
+
+    #[test]
+    pub fn homogeneous_point_rounding() {
+        let v = Vec4i::new(7, -7, 5, 2);
+        assert_eq!(Vec3i::from_homogeneous_point(v), Vec3i::new(3, -3, 2));
+        assert_eq!(Vec3i::from_homogeneous_point_rounded(v), Vec3i::new(4, -4, 3));
+        let v = Vec3i::new(11, -11, 4);
+        assert_eq!(Vec2i::from_homogeneous_point(v), Vec2i::new(2, -2));
+        assert_eq!(Vec2i::from_homogeneous_point_rounded(v), Vec2i::new(3, -3));
+        assert_eq!(Vec2i::from_homogeneous_point_rounded(Vec3i::new(9, 10, -4)), Vec2i::new(-2, -3));
+        assert_eq!(Vec2u::from_homogeneous_point_rounded(Vec3u::new(9, 10, 4)), Vec2u::new(2, 3));
+    }
+
+    #[test]
+    pub fn hex_coordinates() {
+        for &(q, r) in [(0, 0), (2, -1), (-3, 5), (4, 4)].iter() {
+            let cube = Vec2i::new(q, r).axial_to_cube();
+            assert_eq!(cube.x + cube.y + cube.z, 0);
+            assert_eq!(cube.cube_to_axial(), Vec2i::new(q, r));
         }
+        let origin = Vec2i::zero().axial_to_cube();
+        assert_eq!(origin.hex_distance(Vec2i::new(1, 0).axial_to_cube()), 1);
+        assert_eq!(origin.hex_distance(Vec2i::new(1, -1).axial_to_cube()), 1);
+        assert_eq!(origin.hex_distance(Vec2i::new(2, 1).axial_to_cube()), 3);
+        assert_eq!(Vec2i::new(-2, 3).axial_to_cube().hex_distance(Vec2i::new(1, -1).axial_to_cube()), 4);
     }
-}
 
+    #[test]
+    pub fn generic_mul_add() {
+        fn fma<T: MulAdd<Output = T>>(a: T, b: T, c: T) -> T {
+            a.mul_add(b, c)
+        }
+        assert_eq!(fma(3i32, 4, 5), 17);
+        assert_eq!(fma(Vec2i::new(1, -2), Vec2i::new(3, 4), Vec2i::new(5, 6)), Vec2i::new(8, -2));
+        assert_eq!(fma(Vec3u::new(1, 2, 3), Vec3u::broadcast(2), Vec3u::one()), Vec3u::new(3, 5, 7));
+        let v = Vec4i::new(1, 2, 3, 4);
+        assert_eq!(fma(v, v, v), v.mul_add(v, v));
+    }
 
-impl From<Vec3i> for Vec4i {
-    #[inline]
-    fn from(vec: Vec3i) -> Self {
-        Self {
-            x: vec.x,
-            y: vec.y,
-            z: vec.z,
-            w: 0,
+    #[test]
+    pub fn component_min_max_pair() {
+        for v in [Vec4i::new(3, -1, 7, 0), Vec4i::new(-5, -5, -5, -5), Vec4i::new(9, 8, 7, 6)].iter() {
+            assert_eq!(v.component_min_max(), (v.component_min(), v.component_max()));
         }
+        assert_eq!(Vec2u::new(4, 2).component_min_max(), (2, 4));
+        assert_eq!(Vec3i::new(0, i32::MIN, i32::MAX).component_min_max(), (i32::MIN, i32::MAX));
     }
-}
 
-impl From<Vec4i> for Vec3i {
-    #[inline]
-    fn from(vec: Vec4i) -> Self {
-        Self {
-            x: vec.x,
-            y: vec.y,
-            z: vec.z,
+    #[test]
+    pub fn heterogeneous_tuple_conversions() {
+        use core::convert::TryFrom;
+
+        assert_eq!(Vec3i::from((1u8, 2u8, 255u8)), Vec3i::new(1, 2, 255));
+        assert_eq!(Vec2u::from((65535u16, 0u16)), Vec2u::new(65535, 0));
+        assert_eq!(Vec4i::from((1u16, 2u16, 3u16, 4u16)), Vec4i::new(1, 2, 3, 4));
+        assert_eq!(Vec3i::try_from((-1i64, 2i64, 3i64)), Ok(Vec3i::new(-1, 2, 3)));
+        assert!(Vec3i::try_from((0i64, 1i64 << 31, 0i64)).is_err());
+        assert!(Vec2u::try_from((-1i64, 0i64)).is_err());
+        assert_eq!(Vec4u::try_from((1u64, 2u64, 3u64, u64::from(u32::MAX))), Ok(Vec4u::new(1u32, 2, 3, u32::MAX)));
+        assert!(Vec4u::try_from((0u64, 0u64, 0u64, 1u64 << 32)).is_err());
+    }
+
+    #[test]
+    pub fn checked_mul_add_overflow() {
+        let v = Vec3i::new(2, 1 << 16, -3);
+        assert_eq!(v.checked_mul_add(Vec3i::new(3, 2, 4), Vec3i::one()), Some(Vec3i::new(7, 1 << 17 | 1, -11)));
+        assert_eq!(v.checked_mul_add(Vec3i::new(1, 1 << 16, 1), Vec3i::zero()), None);
+        assert_eq!(
+            v.checked_mul_add(Vec3i::new(1, 1 << 15, 1), Vec3i::new(0, i32::MIN, 0)),
+            Some(Vec3i::new(2, 0, -3))
+        );
+        assert_eq!(Vec2u::new(u32::MAX, 1).checked_mul_add(Vec2u::one(), Vec2u::new(1, 0)), None);
+    }
+
+    #[test]
+    pub fn iter_mut_components() {
+        let mut v = Vec3i::new(1, -2, 3);
+        for c in v.iter_mut() {
+            *c += 1;
+        }
+        assert_eq!(v, Vec3i::new(2, -1, 4));
+
+        let mut u = Vec4u::new(1u32, 2, 3, 4);
+        u.iter_mut().enumerate().for_each(|(i, c)| *c *= i as u32);
+        assert_eq!(u, Vec4u::new(0u32, 2, 6, 12));
+    }
+
+    #[test]
+    pub fn median_of_three() {
+        let a = Vec3i::new(1, 9, -4);
+        let b = Vec3i::new(5, 2, 0);
+        let c = Vec3i::new(3, 7, 8);
+        let expected = Vec3i::new(3, 7, 0);
+        assert_eq!(Vec3i::median(a, b, c), expected);
+        assert_eq!(Vec3i::median(c, a, b), expected);
+        assert_eq!(Vec3i::median(b, c, a), expected);
+
+        assert_eq!(Vec2u::median(Vec2u::new(4, 4), Vec2u::new(4, 1), Vec2u::new(0, 6)), Vec2u::new(4, 4));
+        assert_eq!(
+            Vec4i::median(Vec4i::new(20, -20, 0, 1), Vec4i::zero(), Vec4i::new(10, 10, 10, 10)),
+            Vec4i::new(10, 0, 0, 1)
+        );
+    }
+
+    #[test]
+    pub fn abs_max_min() {
+        let v = Vec3i::new(3, -7, 5);
+        assert_eq!(v.abs_max(), 7);
+        assert_eq!(v.abs_min(), 3);
+        assert_eq!(Vec2i::new(i32::MIN, 1).abs_max(), 1 << 31);
+        assert_eq!(Vec4i::new(-2, 4, -1, 0).abs_min(), 0);
+        assert_eq!(Vec4i::new(-2, 4, -1, 9).abs_min(), 1);
+        assert_eq!(Vec2u::new(6, 2).abs_max(), 6);
+        assert_eq!(Vec3u::new(6, 2, 4).abs_min(), 2);
+    }
+
+    #[test]
+    pub fn iter_rect_inclusive_corners() {
+        let cells: Vec<Vec2i> = Vec2i::iter_rect_inclusive(Vec2i::new(1, 0), Vec2i::new(-1, 1)).collect();
+        assert_eq!(
+            cells,
+            vec![
+                Vec2i::new(-1, 0),
+                Vec2i::new(0, 0),
+                Vec2i::new(1, 0),
+                Vec2i::new(-1, 1),
+                Vec2i::new(0, 1),
+                Vec2i::new(1, 1),
+            ]
+        );
+        let single: Vec<Vec2i> = Vec2i::iter_rect_inclusive(Vec2i::new(4, -2), Vec2i::new(4, -2)).collect();
+        assert_eq!(single, vec![Vec2i::new(4, -2)]);
+        assert_eq!(Vec2i::iter_rect_inclusive(Vec2i::new(3, 3), Vec2i::new(0, 0)).count(), 16);
+    }
+
+    #[test]
+    pub fn try_cast_between_signedness() {
+        assert_eq!(Vec3i::new(1, 2, 3).try_cast::<Vec3u>(), Ok(Vec3u::new(1, 2, 3)));
+        assert!(Vec3i::new(1, -2, 3).try_cast::<Vec3u>().is_err());
+        assert_eq!(Vec2u::new(7, i32::MAX as u32).try_cast::<Vec2i>(), Ok(Vec2i::new(7, i32::MAX)));
+        assert!(Vec2u::new(7, 1 << 31).try_cast::<Vec2i>().is_err());
+        assert_eq!(
+            Vec4u::new(0u32, 1, 2, 3).try_cast::<Vec4i>().and_then(|v| v.try_cast::<Vec4u>()),
+            Ok(Vec4u::new(0u32, 1, 2, 3))
+        );
+        assert!(Vec4i::new(0, 0, 0, i32::MIN).try_cast::<Vec4u>().is_err());
+    }
+
+    #[test]
+    pub fn partial_cmp_componentwise_order() {
+        use std::cmp::Ordering;
+        let a = Vec3i::new(1, 2, 3);
+        assert_eq!(a.partial_cmp_componentwise(a), Some(Ordering::Equal));
+        assert_eq!(a.partial_cmp_componentwise(Vec3i::new(1, 2, 4)), Some(Ordering::Less));
+        assert_eq!(a.partial_cmp_componentwise(Vec3i::new(0, 2, -3)), Some(Ordering::Greater));
+        assert_eq!(a.partial_cmp_componentwise(Vec3i::new(2, 1, 3)), None);
+        assert_eq!(Vec2u::new(0, 5).partial_cmp_componentwise(Vec2u::new(5, 0)), None);
+        assert_eq!(Vec4u::zero().partial_cmp_componentwise(Vec4u::one()), Some(Ordering::Less));
+    }
+
+    #[test]
+    pub fn face_normal_axis_aligned() {
+        let o = Vec3i::new(1, 1, 1);
+        assert_eq!(Vec3i::face_normal(o, o + Vec3i::unit_x(), o + Vec3i::unit_y()), Vec3i::unit_z());
+        assert_eq!(Vec3i::face_normal(o, o + Vec3i::unit_y(), o + Vec3i::unit_x()), Vec3i::new(0, 0, -1));
+        assert_eq!(
+            Vec3i::face_normal(Vec3i::zero(), Vec3i::new(0, 3, 0), Vec3i::new(0, 0, 2)),
+            Vec3i::new(6, 0, 0)
+        );
+        assert_eq!(
+            Vec3i::face_normal(Vec3i::new(i32::MIN, 0, 0), Vec3i::new(i32::MAX, 0, 0), Vec3i::new(i32::MIN, 0, 0)),
+            Vec3i::zero()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn face_normal_overflow() {
+        Vec3i::face_normal(Vec3i::zero(), Vec3i::new(1 << 16, 0, 0), Vec3i::new(0, 1 << 16, 0));
+    }
+
+    #[test]
+    pub fn componentwise_shifts() {
+        let v = Vec3u::new(1, 0xF0, u32::MAX);
+        assert_eq!(v.shl_componentwise(Vec3u::new(4, 1, 31)), Vec3u::new(16, 0x1E0, 1 << 31));
+        assert_eq!(v.shr_componentwise(Vec3u::new(0, 4, 28)), Vec3u::new(1, 0xF, 0xF));
+        assert_eq!(v.shl_componentwise(Vec3u::new(32, 33, 0)), Vec3u::new(1, 0x1E0, u32::MAX));
+
+        let i = Vec2i::new(-16, 5);
+        assert_eq!(i.shr_componentwise(Vec2u::new(2, 32)), Vec2i::new(-4, 5));
+        assert_eq!(i.shl_componentwise(Vec2u::new(1, 3)), Vec2i::new(-32, 40));
+        assert_eq!(
+            Vec4i::one().shl_componentwise(Vec4u::new(0u32, 1, 2, 31)),
+            Vec4i::new(1, 2, 4, i32::MIN)
+        );
+    }
+
+    #[test]
+    pub fn weighted_sum_matches_manual() {
+        let (a, b, c) = (Vec3i::new(1, 2, 3), Vec3i::new(-4, 0, 5), Vec3i::new(7, -8, 9));
+        assert_eq!(Vec3i::weighted_sum(&[(2, a), (-1, b), (3, c)]), a * 2 - b + c * 3);
+        assert_eq!(Vec3i::weighted_sum(&[]), Vec3i::zero());
+        assert_eq!(Vec2u::weighted_sum(&[(1, Vec2u::new(3, 4)), (2, Vec2u::new(5, 6))]), Vec2u::new(13, 16));
+
+        // The intermediate terms overflow, but the total fits.
+        let big = Vec4i::broadcast(i32::MAX);
+        assert_eq!(Vec4i::weighted_sum(&[(2, big), (-2, big), (1, Vec4i::one())]), Vec4i::one());
+    }
+
+    #[test]
+    pub fn radix_formatting() {
+        let v = Vec3u::new(5, 6, 0);
+        assert_eq!(format!("{:b}", v), "(101, 110, 0)");
+        assert_eq!(format!("{:#b}", v), "(0b101, 0b110, 0b0)");
+        assert_eq!(format!("{:#06b}", Vec2u::new(5, 1)), "(0b0101, 0b0001)");
+        assert_eq!(format!("{:o}", Vec2u::new(8, 63)), "(10, 77)");
+        assert_eq!(format!("{:#x}", Vec4u::new(255u32, 16, 1, 0)), "(0xff, 0x10, 0x1, 0x0)");
+        assert_eq!(format!("{:X}", Vec2i::new(-1, 171)), "(FFFFFFFF, AB)");
+    }
+
+    #[test]
+    pub fn count_matching_signs() {
+        let v = Vec4i::new(3, 0, -2, 7);
+        assert_eq!(v.count_matching(|c| c > 0), 2);
+        assert_eq!(v.count_matching(|c| c == 0), 1);
+        assert_eq!(v.count_matching(|c| c < 0), 1);
+        assert_eq!(Vec3i::new(0, 0, 5).count_matching(|c| c != 0), 1);
+        assert_eq!(Vec2u::new(4, 9).count_matching(|c| c % 2 == 0), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    pub fn from_f32_saturating_edge_cases() {
+        use crate::vec::{Vec2, Vec3};
+        assert_eq!(Vec3i::from_f32_saturating(Vec3::new(1.9, -1.9, 0.0)), Vec3i::new(1, -1, 0));
+        assert_eq!(
+            Vec3i::from_f32_saturating(Vec3::new(f32::INFINITY, f32::NEG_INFINITY, f32::NAN)),
+            Vec3i::new(i32::MAX, i32::MIN, 0)
+        );
+        assert_eq!(Vec3i::from_f32_saturating(Vec3::new(1e20, -1e20, 3.0)), Vec3i::new(i32::MAX, i32::MIN, 3));
+        assert_eq!(Vec2u::from_f32_saturating(Vec2::new(-5.0, 1e12)), Vec2u::new(0, u32::MAX));
+    }
+
+    #[test]
+    pub fn hilbert_round_trip() {
+        let order = 3;
+        let mut seen = [false; 64];
+        let mut prev: Option<Vec2u> = None;
+        for index in 0..64 {
+            let p = Vec2u::hilbert_decode(index, order);
+            assert_eq!(p.hilbert_encode(order), index);
+            assert!(!seen[(p.y * 8 + p.x) as usize]);
+            seen[(p.y * 8 + p.x) as usize] = true;
+            if let Some(prev) = prev {
+                let (dx, dy) = (prev.x.abs_diff(p.x), prev.y.abs_diff(p.y));
+                assert_eq!(dx + dy, 1);
+            }
+            prev = Some(p);
+        }
+        assert_eq!(Vec2u::hilbert_decode(0, order), Vec2u::zero());
+        assert_eq!(Vec2u::hilbert_decode(63, order), Vec2u::new(7, 0));
+        let far = Vec2u::new(u32::MAX, 12345);
+        assert_eq!(Vec2u::hilbert_decode(far.hilbert_encode(32), 32), far);
+    }
+
+    #[test]
+    pub fn sort_by_distance_sq() {
+        let target = Vec2i::new(1, 1);
+        let mut points = vec![Vec2i::new(5, 1), Vec2i::new(1, 1), Vec2i::new(-1, 0), Vec2i::new(1, 3)];
+        points.sort_by(Vec2i::cmp_by_distance_sq(target));
+        assert_eq!(points, vec![Vec2i::new(1, 1), Vec2i::new(1, 3), Vec2i::new(-1, 0), Vec2i::new(5, 1)]);
+
+        assert_eq!(Vec3i::new(1, 2, 3).distance_sq_to(Vec3i::new(4, -2, 3)), 25);
+        let far = Vec4i::broadcast(i32::MIN).distance_sq_to(Vec4i::broadcast(i32::MAX));
+        assert_eq!(far, 4 * (u32::MAX as u128).pow(2));
+        assert_eq!(Vec2u::new(0, 0).distance_sq_to(Vec2u::new(3, 4)), 25);
+    }
+
+    #[test]
+    pub fn area_and_volume() {
+        assert_eq!(Vec2u::new(16, 9).area(), 144);
+        assert_eq!(Vec2i::new(-3, 4).area(), -12);
+        assert_eq!(Vec3u::new(2, 3, 4).volume(), 24);
+        assert_eq!(Vec3i::new(2, -3, 4).volume(), -24);
+
+        let big = Vec2u::new(1 << 20, 1 << 20);
+        assert!(big.x.checked_mul(big.y).is_none());
+        assert_eq!(big.area_wide(), 1 << 40);
+        assert_eq!(Vec3u::broadcast(u32::MAX).volume_wide(), (u32::MAX as u128).pow(3));
+        assert_eq!(Vec3i::broadcast(i32::MIN).volume_wide(), (i32::MIN as i128).pow(3));
+    }
+
+    #[test]
+    pub fn in_bounds_edges() {
+        let (min, max) = (Vec3i::new(-2, 0, 1), Vec3i::new(2, 4, 3));
+        assert!(min.in_bounds(min, max));
+        assert!(Vec3i::new(1, 3, 2).in_bounds(min, max));
+        assert!(!max.in_bounds(min, max));
+        assert!(!Vec3i::new(-3, 0, 1).in_bounds(min, max));
+        assert!(!Vec3i::new(0, 4, 1).in_bounds(min, max));
+        assert!(!Vec3i::new(0, 1, 0).in_bounds(min, max));
+        assert!(!Vec2u::new(1, 1).in_bounds(Vec2u::new(1, 1), Vec2u::new(1, 5)));
+    }
+
+    #[test]
+    pub fn mul_add_scalar_matches_broadcast() {
+        let (v, offset) = (Vec3i::new(1, -2, 3), Vec3i::new(10, 20, 30));
+        assert_eq!(v.mul_add_scalar(3, offset), v.mul_add(Vec3i::broadcast(3), offset));
+        assert_eq!(v.mul_add_scalar(3, offset), Vec3i::new(13, 14, 39));
+        let u = Vec4u::new(1u32, 2, 3, 4);
+        assert_eq!(u.mul_add_scalar(2, Vec4u::one()), u.mul_add(Vec4u::broadcast(2u32), Vec4u::one()));
+        assert_eq!(Vec2u::new(5, 6).mul_add_scalar(0, Vec2u::new(7, 8)), Vec2u::new(7, 8));
+    }
+
+    #[test]
+    pub fn polygon_area_winding() {
+        let triangle = [Vec2i::new(0, 0), Vec2i::new(4, 0), Vec2i::new(0, 3)];
+        assert_eq!(Vec2i::polygon_area_doubled(&triangle), 12);
+
+        let ccw = [Vec2i::new(-1, -1), Vec2i::new(1, -1), Vec2i::new(1, 1), Vec2i::new(-1, 1)];
+        let mut cw = ccw;
+        cw.reverse();
+        assert_eq!(Vec2i::polygon_area_doubled(&ccw), 8);
+        assert_eq!(Vec2i::polygon_area_doubled(&cw), -8);
+
+        assert_eq!(Vec2i::polygon_area_doubled(&[]), 0);
+        assert_eq!(Vec2i::polygon_area_doubled(&[Vec2i::new(3, 4), Vec2i::new(5, 6)]), 0);
+    }
+
+    #[test]
+    pub fn wrapping_component_indices() {
+        let v = Vec3i::new(10, 20, 30);
+        assert_eq!(v.wrapping_component(0), 10);
+        assert_eq!(v.wrapping_component(-1), 30);
+        assert_eq!(v.wrapping_component(-4), 30);
+        assert_eq!(v.wrapping_component(3), 10);
+        assert_eq!(v.wrapping_component(7), 20);
+        assert_eq!(Vec2u::new(1, 2).wrapping_component(isize::MIN), 1);
+        assert_eq!(Vec4u::new(1u32, 2, 3, 4).wrapping_component(-2), 3);
+    }
+
+    #[test]
+    pub fn checked_from_homogeneous_point_zero() {
+        assert_eq!(Vec2i::checked_from_homogeneous_point(Vec3i::new(4, -6, 2)), Some(Vec2i::new(2, -3)));
+        assert_eq!(Vec2i::checked_from_homogeneous_point(Vec3i::new(4, -6, 0)), None);
+        assert_eq!(Vec2i::checked_from_homogeneous_point(Vec3i::new(i32::MIN, 0, -1)), None);
+        assert_eq!(Vec3u::checked_from_homogeneous_point(Vec4u::new(9u32, 6, 3, 3)), Some(Vec3u::new(3, 2, 1)));
+        assert_eq!(Vec3i::checked_from_homogeneous_point(Vec3i::new(1, 2, 3).into_homogeneous_vector()), None);
+        let p = Vec3i::new(1, 2, 3);
+        assert_eq!(Vec3i::checked_from_homogeneous_point(p.into_homogeneous_point()), Some(p));
+    }
+
+    #[test]
+    pub fn bit_rotations_round_trip() {
+        let v = Vec3u::new(0x8000_0001, 0x1234_5678, 0);
+        assert_eq!(v.rotate_left(4), Vec3u::new(0x0000_0018, 0x2345_6781, 0));
+        assert_eq!(v.rotate_right(1), Vec3u::new(0xC000_0000, 0x091A_2B3C, 0));
+        for n in 0..40 {
+            assert_eq!(v.rotate_left(n).rotate_right(n), v);
+        }
+        assert_eq!(Vec2u::new(1, 2).rotate_left(32), Vec2u::new(1, 2));
+        let w = Vec4u::new(1u32, 2, 3, 4);
+        assert_eq!(w.rotate_right(7).rotate_left(7), w);
+    }
+
+    #[test]
+    pub fn texture_wrap_modes() {
+        let size = Vec2i::new(4, 3);
+        assert_eq!(Vec2i::new(-5, 7).wrap_clamp(size), Vec2i::new(0, 2));
+        assert_eq!(Vec2i::new(2, 1).wrap_clamp(size), Vec2i::new(2, 1));
+        assert_eq!(Vec2i::new(-1, 7).wrap_repeat(size), Vec2i::new(3, 1));
+        assert_eq!(Vec2i::new(-4, 3).wrap_repeat(size), Vec2i::new(0, 0));
+
+        let mirrored: Vec<i32> = (-3..6).map(|y| Vec2i::new(0, y).wrap_mirror(size).y).collect();
+        assert_eq!(mirrored, vec![2, 1, 0, 0, 1, 2, 2, 1, 0]);
+        assert_eq!(Vec2i::new(-1, 0).wrap_mirror(size), Vec2i::new(0, 0));
+        assert_eq!(Vec2i::new(8, 0).wrap_mirror(size), Vec2i::new(0, 0));
+
+        let size = Vec3i::new(2, 5, 1);
+        let p = Vec3i::new(-3, 11, i32::MIN);
+        assert_eq!(p.wrap_clamp(size), Vec3i::new(0, 4, 0));
+        assert_eq!(p.wrap_repeat(size), Vec3i::new(1, 1, 0));
+        assert_eq!(p.wrap_mirror(size), Vec3i::new(1, 1, 0));
+        assert_eq!(Vec3i::new(i32::MAX, 0, 0).wrap_mirror(Vec3i::broadcast(i32::MAX)), Vec3i::new(i32::MAX - 1, 0, 0));
+    }
+
+    #[test]
+    pub fn iter_rev_components() {
+        let v = Vec4i::new(1, 2, 3, 4);
+        let rev: Vec<i32> = v.iter_rev().copied().collect();
+        assert_eq!(rev, vec![v.w, v.z, v.y, v.x]);
+        assert!(v.iter().rev().eq(v.iter_rev()));
+        assert!(Vec3u::new(7, 8, 9).iter().copied().eq(vec![7, 8, 9]));
+        assert_eq!(Vec2i::new(5, 6).iter_rev().next(), Some(&6));
+    }
+
+    #[test]
+    pub fn homogenize_round_trip() {
+        let p = Vec3i::new(2, -3, 5);
+        let scaled = p.into_homogeneous_point() * 4;
+        assert_eq!(scaled.homogenize(), Some(p.into_homogeneous_point()));
+        assert_eq!(Vec3i::from_homogeneous_point(scaled.homogenize().unwrap()), p);
+        assert_eq!(Vec4i::new(4, 6, 2, 4).homogenize(), None);
+        assert_eq!(p.into_homogeneous_vector().homogenize(), None);
+
+        let q = Vec2u::new(3, 7);
+        assert_eq!(Vec3u::new(9, 21, 3).homogenize(), Some(q.into_homogeneous_point()));
+        assert_eq!(Vec2u::from_homogeneous_point(q.into_homogeneous_point()), q);
+        assert_eq!(Vec3i::new(1, 2, 0).homogenize(), None);
+        assert_eq!(Vec3i::new(i32::MIN, 2, -1).homogenize(), None);
+    }
+
+    #[test]
+    pub fn centroid_rounding() {
+        let square = [Vec2i::new(-2, -2), Vec2i::new(4, -2), Vec2i::new(4, 4), Vec2i::new(-2, 4)];
+        assert_eq!(Vec2i::centroid(&square), Vec2i::new(1, 1));
+        assert_eq!(Vec2i::centroid(&[]), Vec2i::zero());
+        // Means of (1/3, -2/3) and (5/2, -5/2).
+        assert_eq!(Vec2i::centroid(&[Vec2i::new(0, 0), Vec2i::new(1, 0), Vec2i::new(0, -2)]), Vec2i::new(0, -1));
+        assert_eq!(Vec2i::centroid(&[Vec2i::new(2, -2), Vec2i::new(3, -3)]), Vec2i::new(3, -3));
+
+        let big = [Vec3i::broadcast(i32::MAX); 3];
+        assert_eq!(Vec3i::centroid(&big), Vec3i::broadcast(i32::MAX));
+        assert_eq!(Vec3i::centroid(&[Vec3i::new(1, 2, 3), Vec3i::new(2, 2, 6)]), Vec3i::new(2, 2, 5));
+    }
+
+    #[test]
+    pub fn int_vector_generic_dot() {
+        fn generic_dot<T: IntVector<Scalar = i32>>(a: T, b: T) -> i32 {
+            a.as_slice().iter().zip(b.as_slice()).map(|(x, y)| x * y).sum()
+        }
+        fn scaled_sum<T: IntVector>(v: T) -> T::Scalar {
+            (v + v).component_sum()
+        }
+
+        let (a, b) = (Vec2i::new(1, 2), Vec2i::new(3, -4));
+        assert_eq!(generic_dot(a, b), IntVector::dot(&a, b));
+        let (a, b) = (Vec4i::new(1, 2, 3, 4), Vec4i::new(5, 6, 7, 8));
+        assert_eq!(generic_dot(a, b), 70);
+        assert_eq!(<Vec3u as IntVector>::DIM, 3);
+        assert_eq!(scaled_sum(Vec3u::new(1, 2, 3)), 12);
+        assert_eq!(<Vec2u as IntVector>::broadcast(7).to_array(), [7, 7]);
+        assert_eq!(Vec4i::from_array([1, 2, 3, 4]), Vec4i::new(1, 2, 3, 4));
+    }
+
+    #[test]
+    pub fn zip_component_pairs() {
+        let pairs: Vec<(i32, i32)> = Vec3i::new(1, 2, 3).zip(Vec3i::new(-1, -2, -3)).collect();
+        assert_eq!(pairs, vec![(1, -1), (2, -2), (3, -3)]);
+        let pairs: Vec<(u32, u32)> = Vec2u::new(4, 5).zip(Vec2u::new(6, 7)).collect();
+        assert_eq!(pairs, vec![(4, 6), (5, 7)]);
+        assert_eq!(Vec4i::one().zip(Vec4i::zero()).count(), 4);
+    }
+
+    #[test]
+    pub fn reflect_across_lines() {
+        let p = Vec2i::new(3, 5);
+        assert_eq!(p.reflect_across_line(Vec2i::unit_x()), Some(Vec2i::new(3, -5)));
+        assert_eq!(p.reflect_across_line(Vec2i::new(-7, 0)), Some(Vec2i::new(3, -5)));
+        assert_eq!(p.reflect_across_line(Vec2i::unit_y()), Some(Vec2i::new(-3, 5)));
+        assert_eq!(p.reflect_across_line(Vec2i::new(1, 1)), Some(Vec2i::new(5, 3)));
+        assert_eq!(p.reflect_across_line(Vec2i::new(1, -1)), Some(Vec2i::new(-5, -3)));
+        // Reflecting (1, 0) across y = 2x gives (-3/5, 4/5).
+        assert_eq!(Vec2i::unit_x().reflect_across_line(Vec2i::new(1, 2)), None);
+        assert_eq!(Vec2i::new(5, 0).reflect_across_line(Vec2i::new(1, 2)), Some(Vec2i::new(-3, 4)));
+        assert_eq!(p.reflect_across_line(Vec2i::zero()), None);
+        assert_eq!(Vec2i::new(i32::MIN, 0).reflect_across_line(Vec2i::unit_y()), None);
+    }
+
+    #[test]
+    pub fn dominant_signed_axis_each_axis() {
+        assert_eq!(Vec3i::new(5, -2, 3).dominant_signed_axis(), (0, 1));
+        assert_eq!(Vec3i::new(-5, -2, 3).dominant_signed_axis(), (0, -1));
+        assert_eq!(Vec3i::new(1, 4, -3).dominant_signed_axis(), (1, 1));
+        assert_eq!(Vec3i::new(1, -4, 3).dominant_signed_axis(), (1, -1));
+        assert_eq!(Vec3i::new(1, 0, 9).dominant_signed_axis(), (2, 1));
+        assert_eq!(Vec3i::new(1, 0, i32::MIN).dominant_signed_axis(), (2, -1));
+        assert_eq!(Vec4i::new(0, 0, 0, -1).dominant_signed_axis(), (3, -1));
+        assert_eq!(Vec2i::new(-3, 3).dominant_signed_axis(), (0, -1));
+        assert_eq!(Vec2i::zero().dominant_signed_axis(), (0, 1));
+    }
+
+    #[test]
+    pub fn overflowing_sub_mask() {
+        let (diff, under) = Vec3u::new(5, 1, 7).overflowing_sub(Vec3u::new(2, 3, 7));
+        assert_eq!(diff, Vec3u::new(3, u32::MAX - 1, 0));
+        assert_eq!(under, BVec3::new(false, true, false));
+        assert!(!Vec2u::new(4, 4).overflowing_sub(Vec2u::new(4, 0)).1.any());
+        let (diff, under) = Vec4u::zero().overflowing_sub(Vec4u::one());
+        assert_eq!(diff, Vec4u::broadcast(u32::MAX));
+        assert!(under.all());
+    }
+
+    #[test]
+    pub fn quadrant_and_octant() {
+        let c = Vec2i::new(10, -10);
+        assert_eq!(Vec2i::new(9, -11).quadrant(c), 0);
+        assert_eq!(Vec2i::new(11, -11).quadrant(c), 1);
+        assert_eq!(Vec2i::new(9, -9).quadrant(c), 2);
+        assert_eq!(Vec2i::new(11, -9).quadrant(c), 3);
+        assert_eq!(c.quadrant(c), 3);
+        assert_eq!(Vec2i::new(10, -11).quadrant(c), 1);
+
+        let c = Vec3i::new(1, 2, 3);
+        for octant in 0..8u8 {
+            let offset = |bit: u8| if octant & bit != 0 { 1 } else { -1 };
+            let p = c + Vec3i::new(offset(1), offset(2), offset(4));
+            assert_eq!(p.octant(c), octant);
+        }
+        assert_eq!(c.octant(c), 7);
+        assert_eq!(Vec3i::new(0, 2, 0).octant(c), 2);
+    }
+
+    #[test]
+    pub fn lerp_percent_steps() {
+        let (a, b) = (Vec2i::new(-10, 0), Vec2i::new(10, 3));
+        assert_eq!(a.lerp_percent(b, 0), a);
+        assert_eq!(a.lerp_percent(b, 50), Vec2i::new(0, 2));
+        assert_eq!(a.lerp_percent(b, 100), b);
+        assert_eq!(a.lerp_percent(b, 250), b);
+        assert_eq!(a.lerp_percent(b, -5), a);
+        assert_eq!(b.lerp_percent(a, 50), Vec2i::new(0, 1));
+
+        let (a, b) = (Vec3u::new(0, 100, u32::MAX), Vec3u::new(100, 0, 0));
+        assert_eq!(a.lerp_percent(b, 25), Vec3u::new(25, 75, 3221225471));
+        assert_eq!(Vec4i::broadcast(i32::MIN).lerp_percent(Vec4i::broadcast(i32::MAX), 100), Vec4i::broadcast(i32::MAX));
+    }
+
+    #[test]
+    pub fn neighbors_8_weighted_costs() {
+        let cell = Vec2i::new(3, -2);
+        let neighbors: Vec<(Vec2i, u32)> = cell.neighbors_8_weighted().collect();
+        assert_eq!(neighbors.len(), 8);
+        assert!(neighbors.contains(&(Vec2i::new(4, -2), 10)));
+        assert!(neighbors.contains(&(Vec2i::new(3, -3), 10)));
+        assert!(neighbors.contains(&(Vec2i::new(2, -1), 14)));
+        assert!(neighbors.iter().all(|&(n, cost)| n.is_adjacent_8(cell) && (cost == 10) == n.is_adjacent_4(cell)));
+    }
+
+    #[test]
+    pub fn map2_to_u8() {
+        let (a, b) = (Vec3i::new(1, 200, -4), Vec3i::new(2, 100, -4));
+        let sums: [u8; 3] = a.map2_to(b, |x, y| (x + y).clamp(0, 255) as u8);
+        assert_eq!(sums, [3, 255, 0]);
+        assert_eq!(a.map2_to(b, |x, y| x == y), [false, false, true]);
+        assert_eq!(Vec2u::new(1, 2).map2_to(Vec2u::new(3, 4), |x, y| (x * y) as u8), [3u8, 8]);
+    }
+
+    #[test]
+    pub fn toroidal_distance_wraps() {
+        let extent = Vec2i::new(10, 8);
+        assert_eq!(Vec2i::new(0, 0).toroidal_distance(Vec2i::new(9, 7), extent), 2);
+        assert_eq!(Vec2i::new(1, 3).toroidal_distance(Vec2i::new(3, 4), extent), 3);
+        assert_eq!(Vec2i::new(0, 0).toroidal_distance(Vec2i::new(5, 4), extent), 9);
+        let extent = Vec3u::new(16, 16, 4);
+        assert_eq!(Vec3u::new(15, 0, 3).toroidal_distance(Vec3u::new(0, 15, 0), extent), 3);
+        assert_eq!(Vec3u::new(2, 2, 2).toroidal_distance(Vec3u::new(2, 2, 2), extent), 0);
+    }
+
+    #[test]
+    pub fn layered_round_trip() {
+        let p = Vec3i::new(4, -5, 6);
+        assert_eq!(p.to_layered(), (Vec2i::new(4, -5), 6));
+        let (xy, layer) = p.to_layered();
+        assert_eq!(Vec3i::from_layered(xy, layer), p);
+        assert_eq!(Vec3i::from_layered(Vec2i::new(1, 2), -3).to_layered(), (Vec2i::new(1, 2), -3));
+    }
+
+    #[test]
+    pub fn abs_branchless_matches_wrapping_abs() {
+        let values = [i32::MIN, i32::MIN + 1, -1000, -1, 0, 1, 1000, i32::MAX];
+        for &a in &values {
+            for &b in &values {
+                let v = Vec2i::new(a, b);
+                assert_eq!(v.abs_branchless(), v.map(i32::wrapping_abs));
+            }
+        }
+        let v = Vec4i::new(-3, 4, i32::MIN, 0);
+        assert_eq!(v.abs_branchless(), Vec4i::new(3, 4, i32::MIN, 0));
+        assert_eq!(Vec3i::new(-7, 7, -1).abs_branchless(), Vec3i::new(7, 7, 1));
+    }
+
+    #[test]
+    pub fn flip_y_within_rows() {
+        assert_eq!(Vec2u::new(3, 0).flip_y_within(480), Vec2u::new(3, 479));
+        assert_eq!(Vec2u::new(3, 479).flip_y_within(480), Vec2u::new(3, 0));
+        assert_eq!(Vec2i::new(-1, 2).flip_y_within(5), Vec2i::new(-1, 2));
+        assert_eq!(Vec2i::new(0, 1).flip_y_within(5), Vec2i::new(0, 3));
+        let p = Vec2i::new(7, 123);
+        assert_eq!(p.flip_y_within(1080).flip_y_within(1080), p);
+    }
+
+    #[test]
+    #[should_panic(expected = "flip_y_within")]
+    pub fn flip_y_within_outside_height() {
+        Vec2u::new(0, 5).flip_y_within(5);
+    }
+
+    #[test]
+    pub fn checked_component_sum_product() {
+        let v = Vec3u::new(1024, 1024, 1024);
+        assert_eq!(v.checked_component_sum(), Some(3072));
+        assert_eq!(v.checked_component_product(), Some(1 << 30));
+        assert_eq!(Vec3u::new(1024, 1024, 4096).checked_component_product(), None);
+        assert_eq!(Vec2u::new(u32::MAX, 1).checked_component_sum(), None);
+        assert_eq!(Vec4i::new(-2, 3, -4, 5).checked_component_product(), Some(120));
+        assert_eq!(Vec4i::new(i32::MIN, -1, 0, 0).checked_component_sum(), None);
+        assert_eq!(Vec2i::new(i32::MIN, -1).checked_component_product(), None);
+        assert_eq!(Vec2i::new(i32::MIN, 0).checked_component_product(), Some(0));
+    }
+
+    #[test]
+    pub fn rotate_components_by_n() {
+        let v = Vec3i::new(1, 2, 3);
+        assert_eq!(v.rotate_components_by(0), v);
+        assert_eq!(v.rotate_components_by(1), v.rotate_components());
+        assert_eq!(v.rotate_components_by(2), Vec3i::new(3, 1, 2));
+        assert_eq!(v.rotate_components_by(3), v);
+        assert_eq!(v.rotate_components_by(7), v.rotate_components());
+        let w = Vec4u::new(1u32, 2, 3, 4);
+        assert_eq!(w.rotate_components_by(2), Vec4u::new(3u32, 4, 1, 2));
+        assert_eq!(w.rotate_components_by(usize::MAX), w.rotate_components_rev());
+    }
+
+    #[test]
+    pub fn u32_bytes_round_trip() {
+        let color = Vec4u::new(0x11u32, 0x22, 0x33, 0x44);
+        assert_eq!(color.to_u32(), 0x4433_2211);
+        assert_eq!(Vec4u::from_u32(0x4433_2211), color);
+        assert_eq!(Vec4u::from_u32(u32::MAX), Vec4u::broadcast(255u32));
+        assert_eq!(Vec4u::broadcast(255u32).to_u32(), u32::MAX);
+        for &packed in &[0, 1, 0x8000_0000, 0xDEAD_BEEF, u32::MAX] {
+            assert_eq!(Vec4u::from_u32(packed).to_u32(), packed);
+        }
+        assert_eq!(Vec4u::new(0x1FFu32, 0, 0, 0).to_u32(), 0xFF);
+        assert_eq!(Vec4u::new(256u32, 0, 0, u32::MAX).to_u32(), 0xFF00_00FF);
+    }
+
+    #[test]
+    pub fn clamp_length_min_scaling() {
+        assert_eq!(Vec2i::new(3, 4).clamp_length_min(10), Vec2i::new(6, 8));
+        assert_eq!(Vec2i::new(0, -1).clamp_length_min(100), Vec2i::new(0, -100));
+        assert_eq!(Vec3i::new(30, 40, 0).clamp_length_min(10), Vec3i::new(30, 40, 0));
+        assert_eq!(Vec3i::new(2, 0, 0).clamp_length_min(2), Vec3i::new(2, 0, 0));
+        assert_eq!(Vec3i::zero().clamp_length_min(10), Vec3i::zero());
+        assert_eq!(Vec4i::new(1, 1, 1, 1).clamp_length_min(-5), Vec4i::new(1, 1, 1, 1));
+        let scaled = Vec4i::new(1, -1, 1, -1).clamp_length_min(1000);
+        assert_eq!(scaled, Vec4i::new(500, -500, 500, -500));
+    }
+
+    #[test]
+    pub fn into_const_array_matching_length() {
+        fn sum<const N: usize>(comps: [i32; N]) -> i32 {
+            comps.iter().sum()
+        }
+        assert_eq!(Vec3i::new(1, 2, 3).into_const_array::<3>(), [1, 2, 3]);
+        assert_eq!(sum(Vec2i::new(4, 5).into_const_array::<2>()), 9);
+        let comps: [u32; 4] = Vec4u::new(1u32, 2, 3, 4).into_const_array();
+        assert_eq!(comps, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    pub fn saturating_pow_clamps() {
+        assert_eq!(Vec3i::new(2, -3, 10).saturating_pow(3), Vec3i::new(8, -27, 1000));
+        assert_eq!(Vec3i::new(2, -3, 1 << 16).saturating_pow(31), Vec3i::new(i32::MAX, i32::MIN, i32::MAX));
+        assert_eq!(Vec2i::new(-2, 5).saturating_pow(0), Vec2i::one());
+        assert_eq!(Vec2u::new(2, 3).saturating_pow(32), Vec2u::new(u32::MAX, u32::MAX));
+        assert_eq!(Vec4u::new(0u32, 1, 2, 16).saturating_pow(8), Vec4u::new(0u32, 1, 256, u32::MAX));
+    }
+
+    #[test]
+    pub fn gcd_lcm_componentwise() {
+        let (a, b) = (Vec3i::new(12, -18, 0), Vec3i::new(8, 27, 5));
+        assert_eq!(a.gcd(b), Vec3i::new(4, 9, 5));
+        assert_eq!(a.lcm(b), Vec3i::new(24, 54, 0));
+        assert_eq!(Vec2u::new(0, 7).gcd(Vec2u::new(9, 0)), Vec2u::new(9, 7));
+        assert_eq!(Vec2u::new(0, 7).lcm(Vec2u::new(9, 0)), Vec2u::zero());
+        assert_eq!(Vec4u::new(4u32, 6, 1, 65536).lcm(Vec4u::new(6u32, 4, 1, 65536)), Vec4u::new(12u32, 12, 1, 65536));
+        assert_eq!(Vec2i::new(i32::MIN, 7).gcd(Vec2i::new(6, -21)), Vec2i::new(2, 7));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn lcm_overflow() {
+        Vec2u::new(65536, 1).lcm(Vec2u::new(65537, 1));
+    }
+
+    #[test]
+    pub fn unit_axis_each() {
+        assert_eq!(Vec2i::unit_axis(0), Vec2i::unit_x());
+        assert_eq!(Vec2i::unit_axis(1), Vec2i::unit_y());
+        assert_eq!(Vec3u::unit_axis(2), Vec3u::unit_z());
+        assert_eq!(Vec4i::unit_axis(3), Vec4i::unit_w());
+        for axis in 0..4 {
+            assert_eq!(Vec4u::unit_axis(axis)[axis], 1);
+            assert_eq!(Vec4u::unit_axis(axis).component_sum(), 1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid axis 3")]
+    pub fn unit_axis_invalid() {
+        Vec3i::unit_axis(3);
+    }
+
+    #[test]
+    pub fn neighbors_3d_counts() {
+        let cell = Vec3i::new(5, -5, 0);
+        let n6: Vec<Vec3i> = cell.neighbors_6().collect();
+        let n18: Vec<Vec3i> = cell.neighbors_18().collect();
+        let n26: Vec<Vec3i> = cell.neighbors_26().collect();
+        assert_eq!((n6.len(), n18.len(), n26.len()), (6, 18, 26));
+        for n in &[&n6, &n18, &n26] {
+            assert!(!n.contains(&cell));
+        }
+        assert!(n6.iter().all(|&n| n.is_adjacent_4(cell)));
+        assert!(n6.iter().all(|n| n18.contains(n)) && n18.iter().all(|n| n26.contains(n)));
+        assert!(n18.contains(&Vec3i::new(6, -4, 0)) && !n18.contains(&Vec3i::new(6, -4, 1)));
+        assert!(n26.iter().all(|&n| n.is_adjacent_8(cell)));
+    }
+
+    #[test]
+    pub fn homogeneous_direction_and_perspective_divide() {
+        let d = Vec3i::new(1, -2, 3);
+        assert_eq!(d.into_homogeneous_direction(), d.into_homogeneous_vector());
+        assert_eq!(Vec2u::new(4, 5).into_homogeneous_direction(), Vec3u::new(4, 5, 0));
+
+        assert_eq!(Vec4i::new(8, -6, 4, 2).perspective_divide(), Some(Vec3i::new(4, -3, 2)));
+        assert_eq!(Vec4i::new(7, 7, 7, 2).perspective_divide(), Some(Vec3i::new(3, 3, 3)));
+        assert_eq!(d.into_homogeneous_point().perspective_divide(), Some(d));
+        assert_eq!(d.into_homogeneous_direction().perspective_divide(), None);
+        assert_eq!(Vec4u::new(3u32, 6, 9, 0).perspective_divide(), None);
+    }
+
+    #[test]
+    pub fn distance_metrics() {
+        let (a, b) = (Vec3i::new(1, -2, 3), Vec3i::new(4, 2, 3));
+        assert_eq!(a.distance::<Manhattan>(b), 7);
+        assert_eq!(a.distance::<Chebyshev>(b), 4);
+        assert_eq!(a.distance::<EuclideanSq>(b), 25);
+        assert_eq!(a.distance::<Manhattan>(a), 0);
+
+        let (a, b) = (Vec2u::new(10, 0), Vec2u::new(0, 10));
+        assert_eq!(a.distance::<Manhattan>(b), 20);
+        assert_eq!(a.distance::<Chebyshev>(b), 10);
+        assert_eq!(a.distance::<EuclideanSq>(b), 200);
+
+        fn path_cost<M: Metric>(path: &[Vec2i]) -> i32 {
+            path.windows(2).map(|w| w[0].distance::<M>(w[1])).sum()
+        }
+        let path = [Vec2i::new(0, 0), Vec2i::new(2, 1), Vec2i::new(2, 5)];
+        assert_eq!(path_cost::<Manhattan>(&path), 7);
+        assert_eq!(path_cost::<Chebyshev>(&path), 6);
+    }
+
+    #[test]
+    pub fn direction_round_trips() {
+        for &dir in &Direction4::ALL {
+            assert_eq!(dir.to_offset().to_direction4(), Some(dir));
+            assert_eq!(Direction8::from(dir).to_offset(), dir.to_offset());
+        }
+        for &dir in &Direction8::ALL {
+            assert_eq!(dir.to_offset().to_direction8(), Some(dir));
+        }
+        assert_eq!(Vec2i::new(0, 1).to_direction4(), Some(Direction4::North));
+        assert_eq!(Vec2i::new(1, -1).to_direction4(), None);
+        assert_eq!(Vec2i::new(1, -1).to_direction8(), Some(Direction8::SouthEast));
+        assert_eq!(Vec2i::new(2, 0).to_direction4(), None);
+        assert_eq!(Vec2i::zero().to_direction8(), None);
+    }
+
+    #[test]
+    pub fn fold_or_and_bits() {
+        let vecs = [Vec3u::new(0b0011, 0b1000, 0b1111), Vec3u::new(0b0110, 0b1001, 0b1010), Vec3u::new(0b0010, 0b1100, 0b1110)];
+        assert_eq!(Vec3u::fold_or(&vecs), Vec3u::new(0b0111, 0b1101, 0b1111));
+        assert_eq!(Vec3u::fold_and(&vecs), Vec3u::new(0b0010, 0b1000, 0b1010));
+        assert_eq!(Vec3u::fold_or(&[]), Vec3u::zero());
+        assert_eq!(Vec3u::fold_and(&[]), Vec3u::broadcast(u32::MAX));
+        assert_eq!(Vec2u::fold_or(&[Vec2u::new(1, 0), Vec2u::new(0, 2)]), Vec2u::new(1, 2));
+        assert_eq!(Vec4u::fold_and(&[Vec4u::broadcast(6u32), Vec4u::new(3u32, 2, 1, 0)]), Vec4u::new(2u32, 2, 0, 0));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    pub fn fill_polygon_cells() {
+        let square = [Vec2i::new(0, 0), Vec2i::new(4, 0), Vec2i::new(4, 4), Vec2i::new(0, 4)];
+        assert_eq!(Vec2i::fill_polygon(&square).count(), 16);
+
+        let quad = [Vec2i::new(2, 0), Vec2i::new(4, 2), Vec2i::new(2, 4), Vec2i::new(0, 2)];
+        let cells: Vec<Vec2i> = Vec2i::fill_polygon(&quad).collect();
+        assert_eq!(cells.len(), 8);
+        assert_eq!(cells[0], Vec2i::new(1, 0));
+        assert!(cells.contains(&Vec2i::new(0, 1)) && !cells.contains(&Vec2i::new(3, 0)));
+
+        let l_shape = [
+            Vec2i::new(0, 0),
+            Vec2i::new(4, 0),
+            Vec2i::new(4, 2),
+            Vec2i::new(2, 2),
+            Vec2i::new(2, 4),
+            Vec2i::new(0, 4),
+        ];
+        let cells: Vec<Vec2i> = Vec2i::fill_polygon(&l_shape).collect();
+        assert_eq!(cells.len(), 12);
+        assert!(cells.contains(&Vec2i::new(3, 1)) && !cells.contains(&Vec2i::new(3, 2)));
+        assert!(cells.iter().all(|c| Vec2i::fill_polygon(&square).any(|s| s == *c)));
+
+        let mut reversed = l_shape;
+        reversed.reverse();
+        assert_eq!(Vec2i::fill_polygon(&reversed).count(), 12);
+        assert_eq!(Vec2i::fill_polygon(&[]).count(), 0);
+    }
+
+    #[test]
+    pub fn checked_neg_min() {
+        assert_eq!(Vec3i::new(1, -2, 0).checked_neg(), Some(Vec3i::new(-1, 2, 0)));
+        assert_eq!(Vec3i::new(1, i32::MIN, 0).checked_neg(), None);
+        assert_eq!(Vec2i::new(i32::MAX, -i32::MAX).checked_neg(), Some(Vec2i::new(-i32::MAX, i32::MAX)));
+        assert_eq!(Vec4i::new(0, 0, 0, i32::MIN).checked_neg(), None);
+    }
+
+    #[test]
+    pub fn pack_1010102_round_trip() {
+        for v in &[
+            Vec4i::new(0, 0, 0, 0),
+            Vec4i::new(1, -1, 511, -2),
+            Vec4i::new(-512, 256, -300, 1),
+            Vec4i::new(511, 511, 511, -1),
+        ] {
+            assert_eq!(Vec4i::unpack_1010102(v.pack_1010102()), *v);
+        }
+        assert_eq!(Vec4i::new(1, 2, 3, 1).pack_1010102(), 1 | 2 << 10 | 3 << 20 | 1 << 30);
+        assert_eq!(Vec4i::new(-1, 0, 0, -1).pack_1010102(), 0x3FF | 3 << 30);
+        assert_eq!(
+            Vec4i::unpack_1010102(Vec4i::new(1000, -1000, i32::MIN, 7).pack_1010102()),
+            Vec4i::new(511, -512, -512, 1)
+        );
+        assert_eq!(Vec4i::unpack_1010102(u32::MAX), Vec4i::broadcast(-1));
+    }
+
+    #[test]
+    pub fn row_and_column_major_indices() {
+        // A 4 wide by 3 tall grid.
+        let (width, height) = (4, 3);
+        let p = Vec2u::new(1, 2);
+        assert_eq!(p.to_index(width), 9);
+        assert_eq!(p.to_index_col_major(height), 5);
+        assert_eq!(Vec2u::from_index(9, width), p);
+        assert_eq!(Vec2u::from_index_col_major(5, height), p);
+        for i in 0..12 {
+            assert_eq!(Vec2i::from_index(i, width as i32).to_index(width as i32), i);
+            assert_eq!(Vec2i::from_index_col_major(i, height as i32).to_index_col_major(height as i32), i);
         }
+        assert_eq!(Vec2i::from_index_col_major(3, 3), Vec2i::new(1, 0));
+        assert_eq!(Vec2i::from_index(3, 4), Vec2i::new(3, 0));
+    }
+
+    #[test]
+    pub fn apply_indexed_scaling() {
+        let mut v = Vec3i::new(5, -6, 7);
+        v.apply_indexed(|i, c| c * i as i32);
+        assert_eq!(v, Vec3i::new(0, -6, 14));
+
+        let scale = [2, 3, 4, 5];
+        let mut u = Vec4u::new(1u32, 1, 2, 2);
+        u.apply_indexed(|i, c| c * scale[i]);
+        assert_eq!(u, Vec4u::new(2u32, 3, 8, 10));
+    }
+
+    #[test]
+    pub fn clamp_to_aabb_outside() {
+        let aabb = IntAabb3::new(Vec3i::new(-1, 0, 2), Vec3i::new(1, 4, 6));
+        assert_eq!(Vec3i::new(5, -3, 4).clamp_to_aabb(&aabb), Vec3i::new(1, 0, 4));
+        assert_eq!(Vec3i::new(-9, 9, 9).clamp_to_aabb(&aabb), Vec3i::new(-1, 4, 6));
+        assert_eq!(Vec3i::new(0, 2, 3).clamp_to_aabb(&aabb), Vec3i::new(0, 2, 3));
+        let aabb = IntAabb2::from_point(Vec2i::new(3, 3));
+        assert_eq!(Vec2i::new(-10, 10).clamp_to_aabb(&aabb), Vec2i::new(3, 3));
+        assert!(aabb.contains(Vec2i::new(i32::MIN, i32::MAX).clamp_to_aabb(&aabb)));
+    }
+
+    #[test]
+    pub fn sample_index_wraps_negative() {
+        let size = Vec2u::new(4, 3);
+        assert_eq!(Vec2i::new(-1, -1).sample_index(size), Vec2u::new(3, 2));
+        assert_eq!(Vec2i::new(-4, -7).sample_index(size), Vec2u::new(0, 2));
+        assert_eq!(Vec2i::new(5, 2).sample_index(size), Vec2u::new(1, 2));
+        assert_eq!(Vec2i::new(i32::MIN, -1).sample_index(Vec2u::new(u32::MAX, u32::MAX)), Vec2u::new((1 << 31) - 1, u32::MAX - 1));
+    }
+
+    #[test]
+    pub fn segments_intersect_cases() {
+        let p = Vec2i::new;
+        // Crossing.
+        assert!(Vec2i::segments_intersect(p(0, 0), p(4, 4), p(0, 4), p(4, 0)));
+        // Touching at an endpoint, and a T junction.
+        assert!(Vec2i::segments_intersect(p(0, 0), p(2, 2), p(2, 2), p(5, 0)));
+        assert!(Vec2i::segments_intersect(p(0, 0), p(4, 0), p(2, 0), p(2, 3)));
+        // Parallel.
+        assert!(!Vec2i::segments_intersect(p(0, 0), p(4, 0), p(0, 1), p(4, 1)));
+        // Collinear, overlapping, touching and disjoint.
+        assert!(Vec2i::segments_intersect(p(0, 0), p(4, 0), p(2, 0), p(6, 0)));
+        assert!(Vec2i::segments_intersect(p(0, 0), p(6, 6), p(2, 2), p(3, 3)));
+        assert!(Vec2i::segments_intersect(p(0, 0), p(4, 0), p(4, 0), p(6, 0)));
+        assert!(!Vec2i::segments_intersect(p(0, 0), p(4, 0), p(5, 0), p(6, 0)));
+        // Not crossing, with one segment's line passing through the other.
+        assert!(!Vec2i::segments_intersect(p(0, 0), p(1, 1), p(3, 0), p(2, 1)));
+        // Extreme coordinates.
+        let (min, max) = (p(i32::MIN, i32::MIN), p(i32::MAX, i32::MAX));
+        assert!(Vec2i::segments_intersect(min, max, p(i32::MIN, i32::MAX), p(i32::MAX, i32::MIN)));
+    }
+
+    #[test]
+    pub fn gather_components() {
+        let v = Vec3i::new(10, 20, 30);
+        assert_eq!(v.gather([2, 1, 0]), Vec3i::new(30, 20, 10));
+        assert_eq!(v.gather([2, 0, 1]), v.zxy());
+        assert_eq!(v.gather([1, 1, 1]), Vec3i::broadcast(20));
+        assert_eq!(Vec4u::new(1u32, 2, 3, 4).gather([3, 3, 0, 1]), Vec4u::new(4u32, 4, 1, 2));
+        assert_eq!(Vec2i::new(5, 6).gather([1, 0]), Vec2i::new(6, 5));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid for vector of type")]
+    pub fn gather_out_of_range() {
+        Vec2i::new(5, 6).gather([0, 2]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    pub fn to_normalized_f32_range() {
+        use crate::vec::{Vec2, Vec4};
+        let v = Vec4u::new(0u32, u32::MAX, u32::MAX / 2, u32::MAX / 4).to_normalized_f32();
+        assert_eq!((v.x, v.y), (0.0, 1.0));
+        assert!((v.z - 0.5).abs() < 1e-6);
+        assert!((v.w - 0.25).abs() < 1e-6);
+        assert_eq!(Vec2u::new(0, u32::MAX).to_normalized_f32(), Vec2::new(0.0, 1.0));
+        assert_eq!(Vec4u::broadcast(u32::MAX).to_normalized_f32(), Vec4::new(1.0, 1.0, 1.0, 1.0));
+        assert_eq!(Vec3u::zero().to_normalized_f32().x, 0.0);
+    }
+
+    #[test]
+    pub fn clamp_ord_matches_clamped() {
+        let v = Vec3i::new(-5, 2, 9);
+        let (min, max) = (Vec3i::broadcast(0), Vec3i::broadcast(4));
+        assert_eq!(v.clamp_ord(min, max), Vec3i::new(0, 2, 4));
+        assert_eq!(Vec2u::new(3, 3).clamp_ord(Vec2u::new(3, 0), Vec2u::new(3, 1)), Vec2u::new(3, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "clamp_ord")]
+    pub fn clamp_ord_inverted_bounds() {
+        Vec4u::broadcast(1u32).clamp_ord(Vec4u::new(0u32, 0, 5, 0), Vec4u::new(9u32, 9, 4, 9));
+    }
+
+    #[test]
+    pub fn scale_rational() {
+        assert_eq!(Vec2i::new(3, -4).scale_rational(5, 0), None);
+        assert_eq!(Vec3i::new(3, -6, 9).scale_rational(2, 3), Some(Vec3i::new(2, -4, 6)));
+        assert_eq!(Vec2i::new(1, -1).scale_rational(1, 2), Some(Vec2i::new(1, -1)));
+        assert_eq!(Vec2i::new(10, 10).scale_rational(1, -3), Some(Vec2i::new(-3, -3)));
+        assert_eq!(Vec4u::new(1u32, 2, 4, 5).scale_rational(1, 3), Some(Vec4u::new(0u32, 1, 1, 2)));
+        assert_eq!(Vec3u::broadcast(u32::MAX).scale_rational(u32::MAX, u32::MAX), Some(Vec3u::broadcast(u32::MAX)));
+        assert_eq!(Vec2u::new(u32::MAX, 1).scale_rational(2, 1), None);
+    }
+
+    #[test]
+    pub fn point_in_polygon() {
+        let square = [Vec2i::new(0, 0), Vec2i::new(4, 0), Vec2i::new(4, 4), Vec2i::new(0, 4)];
+        assert!(Vec2i::point_in_polygon(Vec2i::new(2, 2), &square));
+        assert!(!Vec2i::point_in_polygon(Vec2i::new(5, 2), &square));
+        assert!(!Vec2i::point_in_polygon(Vec2i::new(-1, 4), &square));
+        assert!(Vec2i::point_in_polygon(Vec2i::new(4, 2), &square));
+        assert!(Vec2i::point_in_polygon(Vec2i::new(0, 0), &square));
+        let mut clockwise = square;
+        clockwise.reverse();
+        assert!(Vec2i::point_in_polygon(Vec2i::new(1, 3), &clockwise));
+        assert!(!Vec2i::point_in_polygon(Vec2i::new(1, 5), &clockwise));
+
+        // A "U" shape whose notch spans x in 2..4 above y = 2.
+        let u = [
+            Vec2i::new(0, 0), Vec2i::new(6, 0), Vec2i::new(6, 6), Vec2i::new(4, 6),
+            Vec2i::new(4, 2), Vec2i::new(2, 2), Vec2i::new(2, 6), Vec2i::new(0, 6),
+        ];
+        assert!(Vec2i::point_in_polygon(Vec2i::new(1, 5), &u));
+        assert!(Vec2i::point_in_polygon(Vec2i::new(5, 4), &u));
+        assert!(Vec2i::point_in_polygon(Vec2i::new(3, 1), &u));
+        assert!(!Vec2i::point_in_polygon(Vec2i::new(3, 4), &u));
+        assert!(!Vec2i::point_in_polygon(Vec2i::new(3, 7), &u));
+        assert!(Vec2i::point_in_polygon(Vec2i::new(3, 2), &u));
+        assert!(Vec2i::point_in_polygon(Vec2i::new(2, 4), &u));
+        assert!(Vec2i::point_in_polygon(Vec2i::new(4, 6), &u));
+        assert!(!Vec2i::point_in_polygon(Vec2i::new(7, 2), &u));
+    }
+
+    #[test]
+    pub fn rotate_componentwise() {
+        let v = Vec3u::new(0x8000_0001, 0x0000_00F0, 0x1234_5678);
+        let amounts = Vec3u::new(1, 4, 32);
+        let left = v.rotate_left_componentwise(amounts);
+        assert_eq!(left, Vec3u::new(0x0000_0003, 0x0000_0F00, 0x1234_5678));
+        assert_eq!(left.rotate_right_componentwise(amounts), v);
+        assert_eq!(Vec2u::new(1, 1).rotate_right_componentwise(Vec2u::new(1, 33)), Vec2u::new(0x8000_0000, 0x8000_0000));
+        let w = Vec4u::broadcast(0x0000_0001u32).rotate_left_componentwise(Vec4u::new(0u32, 8, 16, 31));
+        assert_eq!(w, Vec4u::new(1u32, 0x100, 0x1_0000, 0x8000_0000));
+    }
+
+    #[test]
+    pub fn isqrt_rounds_down() {
+        assert_eq!(isqrt_u128(0), 0);
+        assert_eq!(isqrt_u128(1), 1);
+        assert_eq!(isqrt_u128(24), 4);
+        assert_eq!(isqrt_u128(25), 5);
+        assert_eq!(isqrt_u128(1 << 124), 1 << 62);
+        assert_eq!(isqrt_u128(u128::MAX), u128::from(u64::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "extent must be positive")]
+    pub fn toroidal_distance_zero_extent() {
+        Vec2u::new(1, 0).toroidal_distance(Vec2u::zero(), Vec2u::new(4, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "extent must be positive")]
+    pub fn toroidal_distance_negative_extent() {
+        Vec3i::zero().toroidal_distance(Vec3i::one(), Vec3i::new(4, -4, 4));
+    }
+
+    #[test]
+    pub fn iter_ring_large_radius() {
+        let mut ring = Vec2i::iter_ring(Vec2i::zero(), i32::MAX);
+        assert_eq!(ring.next(), Some(Vec2i::broadcast(-i32::MAX)));
+        assert_eq!(ring.next(), Some(Vec2i::new(1 - i32::MAX, -i32::MAX)));
+    }
+
+    #[test]
+    #[should_panic(expected = "iter_ring")]
+    pub fn iter_ring_outside_range() {
+        Vec2i::iter_ring(Vec2i::broadcast(i32::MIN), 1).next();
+    }
+
+    #[test]
+    pub fn hex_distance_distant_cells() {
+        let a = Vec3i::new(i32::MIN / 2, i32::MIN / 2, 0);
+        let b = Vec3i::new(i32::MAX / 2, 0, -(i32::MAX / 2));
+        assert_eq!(a.hex_distance(b), i32::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "Hex distance")]
+    pub fn hex_distance_overflow() {
+        Vec3i::new(i32::MIN, 0, 0).hex_distance(Vec3i::new(i32::MAX, 0, 0));
+    }
+
+    #[test]
+    pub fn axial_to_cube_extremes() {
+        assert_eq!(Vec2i::new(i32::MIN, i32::MAX).axial_to_cube(), Vec3i::new(i32::MIN, i32::MAX, 1));
+        assert_eq!(Vec2i::new(i32::MIN + 1, 0).axial_to_cube(), Vec3i::new(i32::MIN + 1, 0, i32::MAX));
+        assert_eq!(Vec2i::new(i32::MAX, 0).axial_to_cube(), Vec3i::new(i32::MAX, 0, -i32::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cube z coordinate")]
+    pub fn axial_to_cube_overflow() {
+        Vec2i::new(-1, i32::MIN + 1).axial_to_cube();
     }
-}
\ No newline at end of file
+}
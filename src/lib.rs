@@ -65,30 +65,87 @@
 //! and if someone wants to they could implement a Rotor4 which retains all the properties of a Rotor3/Quaternion but does rotation
 //! in 4 dimensions instead, something which simply is not possible to do with Quaternions.
 //! 
+//! `Vec2`, `Vec3` and `Vec4` implement `PartialEq` by comparing their components exactly. For comparisons
+//! with a tolerance, see the `approx` integration below.
+//! 
+//! ### `no_std`
+//! 
+//! The `std` feature is enabled by default. Disabling default features makes the crate `#![no_std]`,
+//! leaving only the integer vector types in the `int` module available, as the floating point types rely
+//! on `std` for their math. The `alloc` feature can be enabled on its own to bring back the `layout()` methods.
+//! 
+//! ### Minimum supported Rust version
+//! 
+//! ultraviolet requires Rust 1.66 or newer.
+//! 
+//! ### Optional integrations
+//! 
+//! The `approx` feature implements the [`approx`](https://docs.rs/approx) crate's `AbsDiffEq`, `RelativeEq`
+//! and `UlpsEq` traits for `Vec2`, `Vec3` and `Vec4`.
+//! 
+//! The `nalgebra` feature adds `From` conversions between the integer vectors and the matching
+//! [`nalgebra`](https://docs.rs/nalgebra) `Vector2`/`Vector3`/`Vector4` and `Point2`/`Point3`/`Point4` types.
+//! It only touches the integer types, so it works without `std` too.
+//! 
+//! The `glam` feature adds `From` conversions between the integer vectors and [`glam`](https://docs.rs/glam)'s
+//! `IVec2`/`IVec3`/`IVec4` and `UVec2`/`UVec3`/`UVec4` types.
+//! glam is built with its `libm` backend rather than `std`, so this also works without `std`.
+//! 
 //! If it's missing something you need it to do, bug me on the [GitHub issue tracker](https://github.com/termhn/ultraviolet/issues) and/or Rust community discord server
 //! (I'm Fusha there) and I'll try to add it for you, if I believe it fits with the vision of the lib :)
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
 mod util;
 
+#[cfg(feature = "std")]
 pub mod bivec;
+#[cfg(feature = "std")]
 pub mod lerp;
+#[cfg(feature = "std")]
 pub mod mat;
+#[cfg(feature = "std")]
 pub mod projection;
+#[cfg(feature = "std")]
 pub mod rotor;
+#[cfg(feature = "std")]
 pub mod transform;
+#[cfg(feature = "std")]
 pub mod vec;
 pub mod int;
+#[cfg(feature = "std")]
 pub mod geometry;
+#[cfg(feature = "std")]
+pub mod grid;
+
+#[cfg(all(feature = "approx", feature = "std"))]
+mod impl_approx;
+#[cfg(feature = "nalgebra")]
+mod impl_nalgebra;
+#[cfg(feature = "glam")]
+mod impl_glam;
 
+#[cfg(feature = "std")]
 pub use bivec::*;
+#[cfg(feature = "std")]
 pub use lerp::*;
+#[cfg(feature = "std")]
 pub use mat::*;
+#[cfg(feature = "std")]
 pub use rotor::*;
+#[cfg(feature = "std")]
 pub use transform::*;
+#[cfg(feature = "std")]
 pub use vec::*;
 pub use int::*;
+#[cfg(feature = "std")]
+pub use grid::*;
 
+#[cfg(feature = "std")]
 pub use wide;
+#[cfg(feature = "std")]
 pub use wide::f32x4;
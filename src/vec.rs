@@ -491,6 +491,13 @@ macro_rules! vec2s {
 
 vec2s!((Vec2, Bivec2, Rotor2, Vec3, Vec4) => f32, (Wec2, WBivec2, WRotor2, Wec3, Wec4) => f32x4);
 
+impl PartialEq for Vec2 {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
 impl From<[Vec2; 4]> for Wec2 {
     #[inline]
     fn from(vecs: [Vec2; 4]) -> Self {
@@ -1099,6 +1106,13 @@ macro_rules! vec3s {
 
 vec3s!((Vec2, Vec3, Bivec3, Rotor3, Vec4) => f32, (Wec2, Wec3, WBivec3, WRotor3, Wec4) => f32x4);
 
+impl PartialEq for Vec3 {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y && self.z == other.z
+    }
+}
+
 impl From<Vec2> for Vec3 {
     #[inline]
     fn from(vec: Vec2) -> Self {
@@ -1696,6 +1710,13 @@ macro_rules! vec4s {
 
 vec4s!(Vec4, Vec2, Vec3 => f32, Wec4, Wec2, Wec3 => f32x4);
 
+impl PartialEq for Vec4 {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y && self.z == other.z && self.w == other.w
+    }
+}
+
 impl From<Vec3> for Vec4 {
     #[inline]
     fn from(vec: Vec3) -> Self {
@@ -0,0 +1,20 @@
+//! Exercises the integer vectors through the public API. Run with
+//! `cargo test --no-default-features --test no_std` to check that they build and
+//! work when the crate is compiled as `#![no_std]`.
+use ultraviolet::int::*;
+
+#[test]
+fn int_vectors_without_std() {
+    let a = Vec3i::new(1, -2, 3);
+    let b = Vec3i::new(-4, 5, 6);
+    assert_eq!(a + b, Vec3i::new(-3, 3, 9));
+    assert_eq!(a.dot(b), 4);
+    assert_eq!(a.cross(b), Vec3i::new(-27, -18, -3));
+    assert_eq!(Vec2u::new(3, 4).mag(), 5);
+    assert_eq!(Vec4i::new(0, -3, 4, 0).mag(), 5);
+    assert_eq!(a.as_slice(), &[1, -2, 3]);
+    assert_eq!(Vec3i::from_le_bytes(a.to_le_bytes()), a);
+
+    let aabb = IntAabb2::from_points([Vec2i::new(1, 2), Vec2i::new(-3, 0)].iter().copied()).unwrap();
+    assert!(aabb.contains(Vec2i::new(0, 1)));
+}